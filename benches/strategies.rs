@@ -0,0 +1,147 @@
+// File: benches/strategies.rs
+//
+// Criterion harness for the logistic regression strategies. The in-binary
+// `--benchmarks` stays for quick checks on real parameter files; this harness
+// uses synthetic data so runs are comparable across machines and commits, and
+// Criterion adds outlier detection, confidence intervals and change tracking
+// against the previous run. Run with `cargo bench`; reports land in
+// target/criterion.
+
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+    Throughput,
+};
+#[cfg(target_arch = "x86_64")]
+use fast_inference::models::logistic::auto::Auto;
+use fast_inference::models::logistic::base::{
+    LogisticRegression, OptimizationStrategy, Sequential,
+};
+#[cfg(target_arch = "aarch64")]
+use fast_inference::models::logistic::simd_arm::NEON;
+#[cfg(target_arch = "x86_64")]
+use fast_inference::models::logistic::simd_x86::{AVXBatch, AVX, AVX2, AVX512, SSE, SSE41};
+use fast_inference::utils::synthetic;
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+use fast_inference::utils::{is_simd_supported, SimdInstructionSet};
+use std::hint::black_box;
+
+// Fixed so every run and machine scores the same generated inputs
+const SEED: u64 = 0;
+
+// Short vectors where the scalar tail and call overhead matter, up to vectors
+// whose samples no longer fit in L2
+const FEATURE_DIMS: [usize; 4] = [16, 128, 1024, 8192];
+const SAMPLE_COUNTS: [usize; 2] = [1, 1000];
+
+// Short vectors where the single-instruction dpps could beat SSE's mul/add loop
+#[cfg(target_arch = "x86_64")]
+const SHORT_FEATURE_DIMS: [usize; 5] = [4, 8, 16, 32, 64];
+
+fn bench_strategy<T: OptimizationStrategy>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    name: &str,
+    strategy: T,
+    weights: &[f32],
+    data: &[f32],
+) {
+    let num_features = weights.len();
+    let num_samples = data.len() / num_features;
+    let model = LogisticRegression::new(weights.to_vec(), 0.1, strategy);
+    let id = BenchmarkId::new(name, format!("{}x{}", num_samples, num_features));
+    group.bench_with_input(id, data, |b, data| {
+        b.iter(|| model.predict_batch(black_box(data), num_features))
+    });
+}
+
+fn logistic_strategies(c: &mut Criterion) {
+    let mut group = c.benchmark_group("logistic");
+    for num_features in FEATURE_DIMS {
+        let weights = &synthetic::linear_params(num_features, SEED)[..num_features];
+        for num_samples in SAMPLE_COUNTS {
+            let data = synthetic::samples(num_features, num_samples, SEED);
+            group.throughput(Throughput::Elements(num_samples as u64));
+
+            bench_strategy(&mut group, "sequential", Sequential, weights, &data);
+
+            // Strategies the CPU lacks would time their scalar fallback, so skip them
+            #[cfg(target_arch = "x86_64")]
+            {
+                if is_simd_supported(SimdInstructionSet::SSE2) {
+                    bench_strategy(&mut group, "sse", SSE, weights, &data);
+                }
+                if is_simd_supported(SimdInstructionSet::SSE4_1) {
+                    bench_strategy(&mut group, "sse41", SSE41, weights, &data);
+                }
+                if is_simd_supported(SimdInstructionSet::AVX) {
+                    bench_strategy(&mut group, "avx", AVX, weights, &data);
+                    bench_strategy(&mut group, "avx_batch", AVXBatch::default(), weights, &data);
+                }
+                if is_simd_supported(SimdInstructionSet::AVX2) {
+                    bench_strategy(&mut group, "avx2", AVX2, weights, &data);
+                }
+                if is_simd_supported(SimdInstructionSet::AVX512) {
+                    bench_strategy(&mut group, "avx512", AVX512, weights, &data);
+                }
+            }
+            #[cfg(target_arch = "aarch64")]
+            if is_simd_supported(SimdInstructionSet::Neon) {
+                bench_strategy(&mut group, "neon", NEON, weights, &data);
+            }
+        }
+    }
+    group.finish();
+}
+
+// SSE against SSE4.1 dpps on short vectors, where the per-sample overhead decides
+#[cfg(target_arch = "x86_64")]
+fn sse41_short_vectors(c: &mut Criterion) {
+    if !is_simd_supported(SimdInstructionSet::SSE4_1) {
+        return;
+    }
+    let mut group = c.benchmark_group("sse41_short");
+    for num_features in SHORT_FEATURE_DIMS {
+        let weights = &synthetic::linear_params(num_features, SEED)[..num_features];
+        let num_samples = 1000;
+        let data = synthetic::samples(num_features, num_samples, SEED);
+        group.throughput(Throughput::Elements(num_samples as u64));
+        bench_strategy(&mut group, "sse", SSE, weights, &data);
+        bench_strategy(&mut group, "sse41", SSE41, weights, &data);
+    }
+    group.finish();
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn sse41_short_vectors(_c: &mut Criterion) {}
+
+// Feature dimensions around the crossovers behind `DimThresholds`
+#[cfg(target_arch = "x86_64")]
+const CROSSOVER_DIMS: [usize; 9] = [4, 8, 16, 32, 128, 512, 768, 1024, 4096];
+
+// Each width against the size-aware Auto, to find where the wider path wins
+#[cfg(target_arch = "x86_64")]
+fn crossover(c: &mut Criterion) {
+    let mut group = c.benchmark_group("crossover");
+    for num_features in CROSSOVER_DIMS {
+        let weights = &synthetic::linear_params(num_features, SEED)[..num_features];
+        let num_samples = 1000;
+        let data = synthetic::samples(num_features, num_samples, SEED);
+        group.throughput(Throughput::Elements(num_samples as u64));
+        bench_strategy(&mut group, "sequential", Sequential, weights, &data);
+        bench_strategy(&mut group, "sse", SSE, weights, &data);
+        if is_simd_supported(SimdInstructionSet::AVX2) {
+            bench_strategy(&mut group, "avx2", AVX2, weights, &data);
+        }
+        if is_simd_supported(SimdInstructionSet::AVX512) {
+            bench_strategy(&mut group, "avx512", AVX512, weights, &data);
+        }
+        let sized = Auto::for_feature_dim(num_features);
+        bench_strategy(&mut group, "auto_sized", sized, weights, &data);
+    }
+    group.finish();
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn crossover(_c: &mut Criterion) {}
+
+criterion_group!(benches, logistic_strategies, sse41_short_vectors, crossover);
+criterion_main!(benches);
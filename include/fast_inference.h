@@ -0,0 +1,64 @@
+#ifndef FAST_INFERENCE_H
+#define FAST_INFERENCE_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Opaque handle to a loaded logistic regression model
+ */
+typedef struct FiModel FiModel;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Loads a logistic regression parameter file (bias last), returning null if
+ * the path is not valid UTF-8 or the file cannot be read
+ *
+ * # Safety
+ *
+ * `path` must be null or point to a NUL-terminated string.
+ */
+FiModel *fi_load_logistic(const char *path);
+
+/**
+ * Number of features the model expects, or 0 for a null handle
+ *
+ * # Safety
+ *
+ * `handle` must be null or a live handle from `fi_load_logistic`.
+ */
+size_t fi_num_features(const FiModel *handle);
+
+/**
+ * Scores one sample, returning the probability or NaN when the handle or
+ * input is null or `len` differs from the model's feature count
+ *
+ * # Safety
+ *
+ * `handle` must be null or a live handle from `fi_load_logistic`, and `input`
+ * must be null or point to `len` readable floats.
+ */
+float fi_predict(const FiModel *handle, const float *input, size_t len);
+
+/**
+ * Releases a model; null is ignored
+ *
+ * # Safety
+ *
+ * `handle` must be null or a handle from `fi_load_logistic` that has not
+ * already been freed.
+ */
+void fi_free(FiModel *handle);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FAST_INFERENCE_H */
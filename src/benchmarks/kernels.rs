@@ -0,0 +1,65 @@
+use std::hint::black_box;
+
+use crate::benchmarks::{BenchmarkConfig, BenchmarkRecord, Suite};
+#[cfg(target_arch = "aarch64")]
+use crate::models::kernels::dot_neon;
+use crate::models::kernels::dot_scalar;
+#[cfg(target_arch = "x86_64")]
+use crate::models::kernels::{dot_avx, dot_avx512, dot_sse};
+
+// 1024 is long enough for the unrolled loops to dominate the scalar tail;
+// 4096 streams more sample data than fits in L2, where memory stalls would show
+const FEATURE_DIMS: [usize; 2] = [1024, 4096];
+const NUM_SAMPLES: usize = 1000;
+
+// Times the raw dot product kernels on synthetic data so the kernels can be
+// compared independently of the parameter and data files
+pub fn run_benchmarks(config: &BenchmarkConfig) -> Vec<BenchmarkRecord> {
+    // The synthetic data already has a fixed size, so --repeat-data does not apply
+    let config = &BenchmarkConfig {
+        repeat_data: 1,
+        ..config.clone()
+    };
+    FEATURE_DIMS
+        .into_iter()
+        .flat_map(|feature_dim| run_dim(feature_dim, config))
+        .collect()
+}
+
+fn run_dim(feature_dim: usize, config: &BenchmarkConfig) -> Vec<BenchmarkRecord> {
+    let weights: Vec<f32> = (0..feature_dim).map(|i| (i as f32 * 0.37).sin()).collect();
+    let data: Vec<f32> = (0..feature_dim * NUM_SAMPLES)
+        .map(|i| (i as f32 * 0.11).cos())
+        .collect();
+
+    let mut suite = Suite::new(config, "kernels", "dot product kernels", feature_dim);
+    suite.info(&format!("NUMBER OF SAMPLES: {}", NUM_SAMPLES));
+
+    suite.run("scalar", "Scalar kernel", &data, |chunk| {
+        black_box(dot_scalar(&weights, chunk));
+    });
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        suite.run("sse", "SSE kernel", &data, |chunk| {
+            black_box(dot_sse(&weights, chunk));
+        });
+
+        suite.run("avx", "AVX kernel (4 accumulators)", &data, |chunk| {
+            black_box(dot_avx(&weights, chunk));
+        });
+
+        suite.run("avx512", "AVX-512 kernel", &data, |chunk| {
+            black_box(dot_avx512(&weights, chunk));
+        });
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        suite.run("neon", "NEON kernel", &data, |chunk| {
+            black_box(dot_neon(&weights, chunk));
+        });
+    }
+
+    suite.finish()
+}
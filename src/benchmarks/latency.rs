@@ -0,0 +1,87 @@
+// File: src/benchmarks/latency.rs
+//
+// Per-sample latency histogram for the benchmarks. Durations are bucketed
+// log-linearly, 16 linear buckets per power of two nanoseconds, so memory stays
+// fixed however many samples are timed while percentiles stay within about 6%.
+
+const SUB_BITS: u32 = 4;
+const SUB_BUCKETS: usize = 1 << SUB_BITS;
+// One group of sub-buckets per possible exponent of a u64
+const NUM_BUCKETS: usize = (64 - SUB_BITS as usize + 1) * SUB_BUCKETS;
+
+pub(crate) struct LatencyHistogram {
+    counts: Vec<u64>,
+    total: u64,
+}
+
+impl LatencyHistogram {
+    pub(crate) fn new() -> Self {
+        Self {
+            counts: vec![0; NUM_BUCKETS],
+            total: 0,
+        }
+    }
+
+    pub(crate) fn record(&mut self, nanos: u64) {
+        self.counts[bucket_index(nanos)] += 1;
+        self.total += 1;
+    }
+
+    /// Latency in nanoseconds below which `p` percent of the samples fall,
+    /// reported as the midpoint of the containing bucket
+    pub(crate) fn percentile(&self, p: f64) -> u64 {
+        let rank = ((p / 100.0 * self.total as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, &count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                let (lower, width) = bucket_bounds(index);
+                return lower + width / 2;
+            }
+        }
+        0
+    }
+}
+
+// Values below SUB_BUCKETS get exact buckets; above that the top SUB_BITS bits
+// after the leading one pick the sub-bucket within the value's power of two
+fn bucket_index(nanos: u64) -> usize {
+    if nanos < SUB_BUCKETS as u64 {
+        return nanos as usize;
+    }
+    let exponent = 63 - nanos.leading_zeros();
+    let shift = exponent - SUB_BITS;
+    let sub = (nanos >> shift) as usize & (SUB_BUCKETS - 1);
+    (shift as usize + 1) * SUB_BUCKETS + sub
+}
+
+// Lower bound and width of a bucket
+fn bucket_bounds(index: usize) -> (u64, u64) {
+    let (group, sub) = (index / SUB_BUCKETS, (index % SUB_BUCKETS) as u64);
+    if group == 0 {
+        return (sub, 1);
+    }
+    let shift = group as u32 - 1;
+    ((SUB_BUCKETS as u64 + sub) << shift, 1 << shift)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_are_within_bucket_error() {
+        let mut histogram = LatencyHistogram::new();
+        for nanos in 1..=10_000 {
+            histogram.record(nanos);
+        }
+        for (p, exact) in [(50.0, 5_000.0), (90.0, 9_000.0), (99.0, 9_900.0)] {
+            let got = histogram.percentile(p) as f64;
+            assert!((got - exact).abs() / exact < 0.07, "p{}: {}", p, got);
+        }
+
+        histogram.record(u64::MAX);
+        assert_eq!(histogram.percentile(0.0), 1);
+        assert!(histogram.percentile(100.0) > 1 << 62);
+    }
+}
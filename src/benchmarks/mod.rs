@@ -1,2 +1,344 @@
+pub mod kernels;
+mod latency;
 pub mod logistic;
 pub mod svm;
+
+use latency::LatencyHistogram;
+use std::time::{Duration, Instant};
+
+/// Trials always completed before `BenchmarkConfig::max_duration` can stop a run,
+/// so the mean and standard deviation rest on more than a single sample
+pub const MIN_TRIALS: usize = 3;
+
+/// How benchmark results are reported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BenchmarkFormat {
+    /// Human readable text printed as each case finishes
+    #[default]
+    Text,
+    /// Records only, for the caller to serialize with `records_to_json`
+    Json,
+}
+
+/// Options shared by every benchmark runner
+#[derive(Debug, Clone)]
+pub struct BenchmarkConfig {
+    pub num_trials: usize,
+    /// Untimed passes over the data before each implementation is timed, so
+    /// cold caches and frequency ramp-up do not land in the first trial
+    pub warmup: usize,
+    /// Also time every sample on its own and report latency percentiles
+    pub latency: bool,
+    /// Passes over the data in every timed trial, so a small data file still
+    /// gives a measurable amount of work. The data is not copied.
+    pub repeat_data: usize,
+    /// Wall-clock budget for the timed trials of each implementation. Once it
+    /// is spent no further trials start, though at least `MIN_TRIALS` always run.
+    pub max_duration: Option<Duration>,
+    pub format: BenchmarkFormat,
+}
+
+/// Result of timing one implementation
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkRecord {
+    /// Suite and case id, such as "logistic/avx"
+    pub implementation: String,
+    pub mean_seconds: f64,
+    pub std_seconds: f64,
+    pub trials: usize,
+    pub feature_dim: usize,
+    /// p50, p90 and p99 per-sample latency in seconds, when requested
+    pub latency_seconds: Option<[f64; 3]>,
+}
+
+impl BenchmarkRecord {
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"impl\": \"{}\", \"mean_seconds\": {}, \"std_seconds\": {}, \"trials\": {}, \"feature_dim\": {}",
+            escape_json(&self.implementation),
+            json_number(self.mean_seconds),
+            json_number(self.std_seconds),
+            self.trials,
+            self.feature_dim
+        );
+        if let Some([p50, p90, p99]) = self.latency_seconds {
+            json.push_str(&format!(
+                ", \"p50_seconds\": {}, \"p90_seconds\": {}, \"p99_seconds\": {}",
+                json_number(p50),
+                json_number(p90),
+                json_number(p99)
+            ));
+        }
+        json.push('}');
+        json
+    }
+}
+
+/// Serializes records as a JSON array with one record per line
+pub fn records_to_json(records: &[BenchmarkRecord]) -> String {
+    let lines: Vec<String> = records
+        .iter()
+        .map(|record| format!("  {}", record.to_json()))
+        .collect();
+    if lines.is_empty() {
+        return "[]\n".to_string();
+    }
+    format!("[\n{}\n]\n", lines.join(",\n"))
+}
+
+/// JSON has no NaN or infinity, so those become null
+pub fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+fn escape_json(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// A group of implementations timed on the same data. Numbers the cases, prints
+// them in text mode and collects a record for each.
+pub(crate) struct Suite<'a> {
+    config: &'a BenchmarkConfig,
+    name: &'static str,
+    feature_dim: usize,
+    records: Vec<BenchmarkRecord>,
+}
+
+impl<'a> Suite<'a> {
+    pub(crate) fn new(
+        config: &'a BenchmarkConfig,
+        name: &'static str,
+        title: &str,
+        feature_dim: usize,
+    ) -> Self {
+        let suite = Self {
+            config,
+            name,
+            feature_dim,
+            records: Vec::new(),
+        };
+        suite.info(&format!("\nBenchmarking {}:", title));
+        suite.info(&format!("FEATURE DIMENSION: {}", feature_dim));
+        suite.info(&format!("NUMBER OF TRIALS: {}", config.num_trials));
+        suite.info(&format!("WARMUP ITERATIONS: {}", config.warmup));
+        if config.repeat_data > 1 {
+            suite.info(&format!("DATA REPEATS PER TRIAL: {}", config.repeat_data));
+        }
+        if let Some(budget) = config.max_duration {
+            suite.info(&format!(
+                "TIME BUDGET PER IMPLEMENTATION: {:.2} seconds",
+                budget.as_secs_f64()
+            ));
+        }
+        suite
+    }
+
+    // Prints a line in text mode only
+    pub(crate) fn info(&self, line: &str) {
+        if self.config.format == BenchmarkFormat::Text {
+            println!("{}", line);
+        }
+    }
+
+    // Times one implementation, plus latency percentiles when requested
+    pub(crate) fn run<P: Fn(&[f32])>(&mut self, id: &str, label: &str, data: &[f32], predict: P) {
+        self.info(&format!("\n{}. {}:", self.records.len() + 1, label));
+        let (num_trials, num_features) = (self.config.num_trials, self.feature_dim);
+
+        for _ in 0..self.config.warmup {
+            for chunk in data.chunks(num_features) {
+                predict(chunk);
+            }
+        }
+
+        let repeats = self.config.repeat_data;
+        let budget = self.config.max_duration;
+        let times = time_trials(data, num_features, num_trials, repeats, budget, &predict);
+        if times.len() < num_trials {
+            self.info(&format!(
+                "Time budget reached after {} of {} trials",
+                times.len(),
+                num_trials
+            ));
+        }
+        let num_trials = times.len();
+        let (mean_seconds, std_seconds) = calculate_stats(&times);
+        self.info(&format!(
+            "Mean Time: {:.2e} ± {:.2e} seconds",
+            mean_seconds, std_seconds
+        ));
+
+        let latency_seconds = self.config.latency.then(|| {
+            let histogram = time_samples(data, num_features, num_trials, repeats, &predict);
+            [50.0, 90.0, 99.0].map(|p| histogram.percentile(p) as f64 / 1e9)
+        });
+        if let Some([p50, p90, p99]) = latency_seconds {
+            self.info(&format!(
+                "Latency p50: {:.2e}, p90: {:.2e}, p99: {:.2e} seconds",
+                p50, p90, p99
+            ));
+        }
+
+        self.records.push(BenchmarkRecord {
+            implementation: format!("{}/{}", self.name, id),
+            mean_seconds,
+            std_seconds,
+            trials: num_trials,
+            feature_dim: num_features,
+            latency_seconds,
+        });
+    }
+
+    // Ends the text report with each case's speedup over the first case
+    pub(crate) fn finish(self) -> Vec<BenchmarkRecord> {
+        for line in speedup_summary(&self.records) {
+            self.info(&line);
+        }
+        self.records
+    }
+}
+
+// Speedup of every case over the suite's first case, the baseline. A case slower
+// than the baseline is flagged, since SIMD losing to scalar code usually means a
+// broken build or a fallback path. Empty with fewer than two cases.
+fn speedup_summary(records: &[BenchmarkRecord]) -> Vec<String> {
+    let [baseline, cases @ ..] = records else {
+        return Vec::new();
+    };
+    if cases.is_empty() {
+        return Vec::new();
+    }
+    let case_id = |record: &BenchmarkRecord| {
+        let id = &record.implementation;
+        id.rsplit_once('/')
+            .map_or(id.clone(), |(_, case)| case.to_string())
+    };
+    let mut lines = vec![format!("\nSpeedup over {}:", case_id(baseline))];
+    for record in cases {
+        let speedup = baseline.mean_seconds / record.mean_seconds;
+        let flag = if speedup < 1.0 {
+            " (slower than baseline)"
+        } else {
+            ""
+        };
+        lines.push(format!("{}: {:.1}x{}", case_id(record), speedup, flag));
+    }
+    lines
+}
+
+pub(crate) fn calculate_stats(times: &[f64]) -> (f64, f64) {
+    let mean = times.iter().sum::<f64>() / times.len() as f64;
+    let variance = times.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / times.len() as f64;
+    let std_dev = variance.sqrt();
+    (mean, std_dev)
+}
+
+// Times up to `num_trials` trials of `predict`, each running `repeats` times over
+// every sample in `data`. No trial past the first `MIN_TRIALS` starts once
+// `budget` has elapsed.
+pub(crate) fn time_trials<P: Fn(&[f32])>(
+    data: &[f32],
+    num_features: usize,
+    num_trials: usize,
+    repeats: usize,
+    budget: Option<Duration>,
+    predict: P,
+) -> Vec<f64> {
+    let mut times = Vec::with_capacity(num_trials);
+    let started = Instant::now();
+    for trial in 0..num_trials {
+        if trial >= MIN_TRIALS && budget.is_some_and(|budget| started.elapsed() >= budget) {
+            break;
+        }
+        let start = Instant::now();
+        for _ in 0..repeats {
+            for chunk in data.chunks(num_features) {
+                predict(chunk);
+            }
+        }
+        times.push(start.elapsed().as_secs_f64());
+    }
+    times
+}
+
+// Times every sample individually over `num_trials` passes. Each sample pays for
+// an extra clock read, so use the batch timings for throughput.
+pub(crate) fn time_samples<P: Fn(&[f32])>(
+    data: &[f32],
+    num_features: usize,
+    num_trials: usize,
+    repeats: usize,
+    predict: P,
+) -> LatencyHistogram {
+    let mut histogram = LatencyHistogram::new();
+    for _ in 0..num_trials * repeats {
+        for chunk in data.chunks(num_features) {
+            let start = Instant::now();
+            predict(chunk);
+            histogram.record(start.elapsed().as_nanos() as u64);
+        }
+    }
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_serialize_to_json() {
+        let record = BenchmarkRecord {
+            implementation: "logistic/avx".to_string(),
+            mean_seconds: 0.5,
+            std_seconds: f64::NAN,
+            trials: 3,
+            feature_dim: 8,
+            latency_seconds: None,
+        };
+        assert_eq!(
+            records_to_json(&[record]),
+            "[\n  {\"impl\": \"logistic/avx\", \"mean_seconds\": 0.5, \"std_seconds\": null, \"trials\": 3, \"feature_dim\": 8}\n]\n"
+        );
+        assert_eq!(records_to_json(&[]), "[]\n");
+    }
+
+    #[test]
+    fn summary_flags_cases_slower_than_the_baseline() {
+        let record = |id: &str, mean_seconds| BenchmarkRecord {
+            implementation: format!("logistic/{}", id),
+            mean_seconds,
+            std_seconds: 0.0,
+            trials: 3,
+            feature_dim: 8,
+            latency_seconds: None,
+        };
+        let records = [
+            record("sequential", 0.58),
+            record("avx", 0.1),
+            record("sse41", 0.8),
+        ];
+        assert_eq!(
+            speedup_summary(&records),
+            vec![
+                "\nSpeedup over sequential:",
+                "avx: 5.8x",
+                "sse41: 0.7x (slower than baseline)"
+            ]
+        );
+        assert!(speedup_summary(&records[..1]).is_empty());
+    }
+
+    #[test]
+    fn time_budget_stops_after_the_minimum_trials() {
+        let data = [0.0f32; 4];
+        let slow = |_: &[f32]| std::thread::sleep(Duration::from_millis(1));
+        let budget = Some(Duration::ZERO);
+        assert_eq!(time_trials(&data, 2, 50, 1, budget, slow).len(), MIN_TRIALS);
+        assert_eq!(time_trials(&data, 2, 2, 1, budget, slow).len(), 2);
+        assert_eq!(time_trials(&data, 2, 5, 1, None, slow).len(), 5);
+    }
+}
@@ -1 +1,77 @@
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
 
+use crate::benchmarks::{BenchmarkConfig, BenchmarkRecord, Suite};
+#[cfg(target_arch = "aarch64")]
+use crate::models::strategy::NEON;
+#[cfg(target_arch = "x86_64")]
+use crate::models::strategy::{AVX, AVX2, AVX512, SSE};
+use crate::models::svm::base::{Sequential, SupportVectorMachine};
+use crate::utils::io::{read_f32_binary, read_params, BiasPosition, Endian};
+
+pub fn run_benchmarks(
+    params_path: &Path,
+    data_path: &Path,
+    bias_position: BiasPosition,
+    config: &BenchmarkConfig,
+) -> io::Result<Vec<BenchmarkRecord>> {
+    // Read parameters file and split into weights and bias
+    let (weights, bias) = read_params(params_path, bias_position)?;
+
+    // Read data matrix
+    let data = read_f32_binary(data_path, Endian::Little)?;
+
+    let num_features = weights.len();
+
+    let mut suite = Suite::new(config, "svm", "SVM implementations", num_features);
+
+    // Baseline sequential implementation
+    let model_seq = SupportVectorMachine::new(weights.clone(), bias, Sequential);
+    suite.run(
+        "sequential",
+        "Baseline sequential implementation",
+        &data,
+        |chunk| {
+            black_box(model_seq.predict(chunk));
+        },
+    );
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        // SSE implementation
+        let model_sse = SupportVectorMachine::new(weights.clone(), bias, SSE);
+        suite.run("sse", "SSE SIMD implementation", &data, |chunk| {
+            black_box(model_sse.predict(chunk));
+        });
+
+        // AVX implementation
+        let model_avx = SupportVectorMachine::new(weights.clone(), bias, AVX);
+        suite.run("avx", "AVX SIMD implementation", &data, |chunk| {
+            black_box(model_avx.predict(chunk));
+        });
+
+        // AVX2 implementation
+        let model_avx2 = SupportVectorMachine::new(weights.clone(), bias, AVX2);
+        suite.run("avx2", "AVX2 (FMA) SIMD implementation", &data, |chunk| {
+            black_box(model_avx2.predict(chunk));
+        });
+
+        // AVX-512 implementation
+        let model_avx512 = SupportVectorMachine::new(weights.clone(), bias, AVX512);
+        suite.run("avx512", "AVX-512 SIMD implementation", &data, |chunk| {
+            black_box(model_avx512.predict(chunk));
+        });
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        // NEON implementation
+        let model_neon = SupportVectorMachine::new(weights.clone(), bias, NEON);
+        suite.run("neon", "ARM NEON implementation", &data, |chunk| {
+            black_box(model_neon.predict(chunk));
+        });
+    }
+
+    Ok(suite.finish())
+}
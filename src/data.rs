@@ -0,0 +1,137 @@
+// File: src/data.rs
+//
+// Row-major sample matrix returned by the data loaders. The shape is checked
+// once when the matrix is built, so the inference loops can walk whole rows
+// without re-deriving the sample count from a flat buffer and a feature count.
+
+use crate::utils::io::gather_columns;
+use std::io;
+use std::slice::{Chunks, ChunksExact};
+
+/// Row-major matrix of f32 samples, one row per sample
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    data: Vec<f32>,
+    rows: usize,
+    cols: usize,
+}
+
+impl Matrix {
+    /// Wraps a row-major buffer, failing unless it holds a whole number of
+    /// rows of `cols` values
+    pub fn new(data: Vec<f32>, cols: usize) -> io::Result<Self> {
+        match data.len().checked_div(cols) {
+            Some(rows) if rows * cols == data.len() => Ok(Self { data, rows, cols }),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "data holds {} values, which is not a multiple of the feature dimension {}",
+                    data.len(),
+                    cols
+                ),
+            )),
+        }
+    }
+
+    /// Builds the matrix from a column-major buffer of `cols` columns
+    pub fn from_column_major(data: Vec<f32>, cols: usize) -> io::Result<Self> {
+        let columns = Self::new(data, cols)?;
+        Ok(Self {
+            data: gather_columns(&columns.data, cols),
+            ..columns
+        })
+    }
+
+    /// Zero-pads every row to `cols` values, matching a model built with
+    /// `pad_to_lanes`. Panics when `cols` is narrower than the matrix.
+    pub fn pad_cols(self, cols: usize) -> Self {
+        assert!(
+            cols >= self.cols,
+            "cannot pad {} columns to {}",
+            self.cols,
+            cols
+        );
+        if cols == self.cols {
+            return self;
+        }
+        let mut data = Vec::with_capacity(self.rows * cols);
+        for row in self.rows() {
+            data.extend_from_slice(row);
+            data.resize(data.len() + cols - self.cols, 0.0);
+        }
+        Self {
+            data,
+            rows: self.rows,
+            cols,
+        }
+    }
+
+    /// Number of samples
+    pub fn num_rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of features per sample
+    pub fn num_cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Features of sample `i`; panics when `i` is out of range
+    pub fn row(&self, i: usize) -> &[f32] {
+        assert!(
+            i < self.rows,
+            "row {} out of range for {} rows",
+            i,
+            self.rows
+        );
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Iterates over the samples in order
+    pub fn rows(&self) -> ChunksExact<'_, f32> {
+        self.data.chunks_exact(self.cols)
+    }
+
+    /// Row-major blocks of at most `rows_per_block` samples each
+    pub fn row_blocks(&self, rows_per_block: usize) -> Chunks<'_, f32> {
+        self.data.chunks(rows_per_block.max(1) * self.cols)
+    }
+
+    /// The whole matrix as one row-major slice
+    pub fn as_slice(&self) -> &[f32] {
+        &self.data
+    }
+
+    pub fn into_vec(self) -> Vec<f32> {
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_the_shape_and_walks_rows() {
+        let matrix = Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 2).unwrap();
+        assert_eq!((matrix.num_rows(), matrix.num_cols()), (3, 2));
+        assert_eq!(matrix.row(1), &[3.0, 4.0]);
+        assert_eq!(
+            matrix.rows().collect::<Vec<_>>(),
+            vec![&[1.0, 2.0], &[3.0, 4.0], &[5.0, 6.0]]
+        );
+        let blocks: Vec<usize> = matrix.row_blocks(2).map(|block| block.len()).collect();
+        assert_eq!(blocks, vec![4, 2]);
+
+        let columns = Matrix::from_column_major(vec![1.0, 3.0, 5.0, 2.0, 4.0, 6.0], 2).unwrap();
+        assert_eq!(columns, matrix);
+
+        let padded = matrix.clone().pad_cols(4);
+        assert_eq!(padded.num_rows(), 3);
+        assert_eq!(padded.row(2), &[5.0, 6.0, 0.0, 0.0]);
+        assert_eq!(matrix.clone().pad_cols(2), matrix);
+
+        assert!(Matrix::new(vec![1.0, 2.0, 3.0], 2).is_err());
+        assert!(Matrix::new(vec![], 0).is_err());
+    }
+}
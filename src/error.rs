@@ -0,0 +1,102 @@
+// File: src/error.rs
+//
+// Crate-level error for callers that need to tell failures apart, e.g. a server
+// answering a malformed request differently from a broken deployment. Model
+// methods keep returning `InferenceError`; everything here converts into this
+// type with `?`.
+//
+// Most readers still return `io::Result`. They carry these errors as the inner
+// error of an `InvalidData` io::Error, and converting back recovers the variant.
+
+use crate::models::error::InferenceError;
+use crate::utils::SimdInstructionSet;
+use std::io;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(io::Error),
+    /// Input length does not match the number of model features
+    #[error("input dimension mismatch: expected {expected} features, got {got}")]
+    DimensionMismatch { expected: usize, got: usize },
+    /// File ends part way through a value or a sample
+    #[error("file holds {len} bytes, which is not a whole number of {record_len}-byte records")]
+    TruncatedFile { len: usize, record_len: usize },
+    /// Requested instruction set is not available on the running CPU
+    #[error("this CPU does not support {0:?}")]
+    UnsupportedSimd(SimdInstructionSet),
+    /// Parameter file header is present but cannot be parsed
+    #[error("malformed parameter header: {0}")]
+    MalformedHeader(String),
+    /// Any other model error
+    #[error(transparent)]
+    Inference(InferenceError),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        // Recover the variant when an io::Result reader carried one of ours
+        if error.get_ref().is_some_and(|inner| inner.is::<Error>()) {
+            return *error.into_inner().unwrap().downcast().unwrap();
+        }
+        Error::Io(error)
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Io(error) => error,
+            other => io::Error::new(io::ErrorKind::InvalidData, other),
+        }
+    }
+}
+
+impl From<InferenceError> for Error {
+    fn from(error: InferenceError) -> Self {
+        match error {
+            InferenceError::DimensionMismatch { expected, got } => {
+                Error::DimensionMismatch { expected, got }
+            }
+            other => Error::Inference(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variants_survive_a_round_trip_through_io_error() {
+        let truncated = Error::TruncatedFile {
+            len: 6,
+            record_len: 4,
+        };
+        let io_error = io::Error::from(truncated);
+        assert_eq!(io_error.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(
+            Error::from(io_error),
+            Error::TruncatedFile {
+                len: 6,
+                record_len: 4
+            }
+        ));
+
+        let missing = io::Error::new(io::ErrorKind::NotFound, "missing");
+        assert!(matches!(Error::from(missing), Error::Io(_)));
+        let mismatch = InferenceError::DimensionMismatch {
+            expected: 3,
+            got: 2,
+        };
+        assert!(matches!(
+            Error::from(mismatch),
+            Error::DimensionMismatch {
+                expected: 3,
+                got: 2
+            }
+        ));
+    }
+}
@@ -0,0 +1,120 @@
+// File: src/ffi.rs
+//
+// C ABI for embedding the engine in non-Rust hosts. A model is loaded into an
+// opaque heap handle, scored one sample at a time and released by the caller.
+// Nothing here panics across the boundary: a failed load returns a null handle
+// and a failed prediction returns NaN.
+//
+// The matching C declarations live in include/fast_inference.h, generated with
+//
+//     cbindgen --config cbindgen.toml --output include/fast_inference.h
+
+use crate::models::logistic::auto::Auto;
+use crate::models::logistic::base::LogisticRegression;
+use crate::utils::io::BiasPosition;
+use std::ffi::{c_char, CStr};
+use std::path::Path;
+use std::slice;
+
+/// Opaque handle to a loaded logistic regression model
+pub struct FiModel {
+    model: LogisticRegression<f32, Auto>,
+}
+
+/// Loads a logistic regression parameter file (bias last), returning null if
+/// the path is not valid UTF-8 or the file cannot be read
+///
+/// # Safety
+///
+/// `path` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn fi_load_logistic(path: *const c_char) -> *mut FiModel {
+    if path.is_null() {
+        return std::ptr::null_mut();
+    }
+    let Ok(path) = CStr::from_ptr(path).to_str() else {
+        return std::ptr::null_mut();
+    };
+    match LogisticRegression::from_param_file(Path::new(path), BiasPosition::Last, Auto::new()) {
+        Ok(model) => Box::into_raw(Box::new(FiModel { model })),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Number of features the model expects, or 0 for a null handle
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle from `fi_load_logistic`.
+#[no_mangle]
+pub unsafe extern "C" fn fi_num_features(handle: *const FiModel) -> usize {
+    match handle.as_ref() {
+        Some(handle) => handle.model.num_features(),
+        None => 0,
+    }
+}
+
+/// Scores one sample, returning the probability or NaN when the handle or
+/// input is null or `len` differs from the model's feature count
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle from `fi_load_logistic`, and `input`
+/// must be null or point to `len` readable floats.
+#[no_mangle]
+pub unsafe extern "C" fn fi_predict(handle: *const FiModel, input: *const f32, len: usize) -> f32 {
+    let Some(handle) = handle.as_ref() else {
+        return f32::NAN;
+    };
+    if input.is_null() {
+        return f32::NAN;
+    }
+    let input = slice::from_raw_parts(input, len);
+    handle.model.try_predict(input).unwrap_or(f32::NAN)
+}
+
+/// Releases a model; null is ignored
+///
+/// # Safety
+///
+/// `handle` must be null or a handle from `fi_load_logistic` that has not
+/// already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn fi_free(handle: *mut FiModel) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+    use std::fs;
+
+    #[test]
+    fn loads_predicts_and_reports_errors() {
+        let path = std::env::temp_dir().join(format!("fast_inference_ffi_{}", std::process::id()));
+        let bytes: Vec<u8> = [1.0f32, -1.0, 0.0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        fs::write(&path, bytes).unwrap();
+        let c_path = CString::new(path.to_str().unwrap()).unwrap();
+
+        unsafe {
+            let handle = fi_load_logistic(c_path.as_ptr());
+            assert!(!handle.is_null());
+            assert_eq!(fi_num_features(handle), 2);
+            assert!(fi_predict(handle, [2.0, 1.0].as_ptr(), 2) > 0.5);
+            assert!(fi_predict(handle, [2.0].as_ptr(), 1).is_nan());
+            assert!(fi_predict(handle, std::ptr::null(), 2).is_nan());
+            fi_free(handle);
+
+            let missing = CString::new("/nonexistent/params.bin").unwrap();
+            assert!(fi_load_logistic(missing.as_ptr()).is_null());
+            assert!(fi_predict(std::ptr::null(), [2.0, 1.0].as_ptr(), 2).is_nan());
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
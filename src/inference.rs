@@ -0,0 +1,72 @@
+// File: src/inference.rs
+//
+// One-call entry points for using the crate as a library: load a raw binary
+// parameter file (bias last) and data file, run the model over every sample and
+// return the predictions. The CLI reads binary data files through `load_samples`
+// too, and adds the other file formats, output options and threading controls.
+
+use crate::data::Matrix;
+use crate::error::Result;
+use crate::models::logistic::auto::Auto;
+use crate::models::logistic::base::LogisticRegression;
+use crate::models::svm::base::{Sequential, SupportVectorMachine};
+use crate::utils::io::{read_binary, BiasPosition, DType, Endian};
+use std::path::Path;
+
+/// Runs logistic regression over every sample, returning 1 where the
+/// probability is above 0.5 and 0 otherwise
+pub fn run_logistic(params: &Path, data: &Path) -> Result<Vec<u8>> {
+    let model = LogisticRegression::from_param_file(params, BiasPosition::Last, Auto::new())?;
+    let data = load_samples(data, DType::F32, Endian::Little, model.num_features())?;
+    Ok(model
+        .predict_batch(data.as_slice(), data.num_cols())
+        .into_iter()
+        .map(|prob| if prob > 0.5 { 1 } else { 0 })
+        .collect())
+}
+
+/// Runs a linear SVM over every sample, returning the predicted labels (1 or -1)
+pub fn run_svm(params: &Path, data: &Path) -> Result<Vec<i32>> {
+    let model = SupportVectorMachine::from_param_file(params, BiasPosition::Last, Sequential)?;
+    let data = load_samples(data, DType::F32, Endian::Little, model.num_features())?;
+    Ok(model.predict_batch(data.as_slice(), data.num_cols()))
+}
+
+/// Reads a row-major binary data file of `num_features` columns
+pub fn load_samples(
+    path: &Path,
+    dtype: DType,
+    endian: Endian,
+    num_features: usize,
+) -> Result<Matrix> {
+    let data = read_binary(path, dtype, endian)?;
+    Ok(Matrix::new(data, num_features)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use std::fs;
+
+    fn write_f32s(path: &Path, values: &[f32]) {
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn runs_models_from_files() {
+        let dir = std::env::temp_dir().join(format!("fast_inference_run_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (params, data) = (dir.join("params.bin"), dir.join("data.bin"));
+        write_f32s(&params, &[1.0, -1.0, 0.0]);
+        write_f32s(&data, &[2.0, 1.0, 1.0, 2.0]);
+
+        assert_eq!(run_logistic(&params, &data).unwrap(), vec![1, 0]);
+        assert_eq!(run_svm(&params, &data).unwrap(), vec![1, -1]);
+
+        write_f32s(&data, &[2.0, 1.0, 1.0]);
+        assert!(matches!(run_logistic(&params, &data), Err(Error::Io(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
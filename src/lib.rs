@@ -1,3 +1,15 @@
+// std::simd is still unstable, so the portable kernels need a nightly toolchain
+#![cfg_attr(feature = "portable_simd", feature(portable_simd))]
+
 pub mod benchmarks;
+pub mod data;
+pub mod error;
+pub mod ffi;
+pub mod inference;
 pub mod models;
+#[cfg(feature = "rayon")]
+pub mod pool;
 pub mod utils;
+
+pub use error::Error;
+pub use inference::{run_logistic, run_svm};
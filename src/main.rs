@@ -1,23 +1,46 @@
 use clap::ValueHint;
-use fast_inference::benchmarks;
-use fast_inference::models::logistic::base::{
-    LogisticRegression, Sequential as LogisticSequential,
+use fast_inference::benchmarks::{self, BenchmarkConfig, BenchmarkFormat};
+use fast_inference::data::Matrix;
+use fast_inference::inference;
+use fast_inference::models::ensemble::{Aggregation, Ensemble};
+use fast_inference::models::error::InferenceError;
+use fast_inference::models::knn::brute_force::Knn;
+use fast_inference::models::logistic::activation::sigmoid;
+use fast_inference::models::logistic::auto::Auto;
+use fast_inference::models::logistic::base::{LogisticRegression, Sequential};
+use fast_inference::models::logistic::clip::Clip;
+use fast_inference::models::logistic::quantized::QuantizedLogisticRegression;
+use fast_inference::models::naive_bayes::gaussian::GaussianNaiveBayes;
+use fast_inference::models::svm::base::{
+    Sequential as SVMSequential, SupportVectorMachine, TieBreak,
 };
-use fast_inference::models::svm::base::{Sequential as SVMSequential, SupportVectorMachine};
+#[cfg(feature = "rayon")]
+use fast_inference::pool::InferencePool;
+use fast_inference::utils::header::{ModelKind, ParamHeader};
+use fast_inference::utils::metrics::ConfusionMatrix;
+use fast_inference::utils::{self, io, npy, validate, SimdInstructionSet};
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use clap::{Parser, ValueEnum};
+use log::{info, warn};
 
 use std::fmt::{Display, Formatter};
-#[cfg(target_arch = "x86_64")]
 use std::fs::File;
-use std::io::{BufReader, Read, Write};
-use std::path::PathBuf;
+use std::io::{BufWriter, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "rayon")]
+use std::sync::OnceLock;
+use std::time::Duration;
 
-#[derive(Clone, Debug, ValueEnum)]
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum ModelType {
     Logistic,
     Svm,
+    /// Gaussian naive Bayes
+    NaiveBayes,
+    /// Brute-force k-nearest neighbors over the training set in the parameter file
+    Knn,
 }
 
 impl Display for ModelType {
@@ -25,180 +48,2040 @@ impl Display for ModelType {
         match self {
             ModelType::Logistic => write!(f, "Logistic"),
             ModelType::Svm => write!(f, "SVM"),
+            ModelType::NaiveBayes => write!(f, "Naive Bayes"),
+            ModelType::Knn => write!(f, "KNN"),
+        }
+    }
+}
+
+impl From<ModelKind> for ModelType {
+    fn from(kind: ModelKind) -> Self {
+        match kind {
+            ModelKind::Logistic => ModelType::Logistic,
+            ModelKind::Svm => ModelType::Svm,
+            ModelKind::NaiveBayes => ModelType::NaiveBayes,
+            ModelKind::Knn => ModelType::Knn,
+        }
+    }
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum DataFormat {
+    /// Raw f32 values, little-endian unless --endian big
+    Binary,
+    /// Comma separated rows, one sample per row
+    Csv,
+    /// NumPy float32 array, 2D data arrays are shaped (samples, features)
+    Npy,
+}
+
+impl Display for DataFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataFormat::Binary => write!(f, "binary"),
+            DataFormat::Csv => write!(f, "csv"),
+            DataFormat::Npy => write!(f, "npy"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum DataLayout {
+    /// Row-major: each sample's features are contiguous
+    Row,
+    /// Column-major: each feature's values across all samples are contiguous
+    Col,
+}
+
+impl Display for DataLayout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataLayout::Row => write!(f, "row"),
+            DataLayout::Col => write!(f, "col"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum DataType {
+    F32,
+    /// Half precision, upcast to f32 on read
+    F16,
+}
+
+impl Display for DataType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataType::F32 => write!(f, "f32"),
+            DataType::F16 => write!(f, "f16"),
+        }
+    }
+}
+
+impl From<&DataType> for io::DType {
+    fn from(dtype: &DataType) -> Self {
+        match dtype {
+            DataType::F32 => io::DType::F32,
+            DataType::F16 => io::DType::F16,
+        }
+    }
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum ByteOrder {
+    Little,
+    Big,
+}
+
+impl Display for ByteOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ByteOrder::Little => write!(f, "little"),
+            ByteOrder::Big => write!(f, "big"),
+        }
+    }
+}
+
+impl From<&ByteOrder> for io::Endian {
+    fn from(order: &ByteOrder) -> Self {
+        match order {
+            ByteOrder::Little => io::Endian::Little,
+            ByteOrder::Big => io::Endian::Big,
+        }
+    }
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum BiasLayout {
+    /// Weights followed by the bias
+    Last,
+    /// Bias followed by the weights
+    First,
+    /// Weights only, the bias is 0, e.g. scikit-learn models fitted with
+    /// fit_intercept=False
+    None,
+}
+
+impl Display for BiasLayout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BiasLayout::Last => write!(f, "last"),
+            BiasLayout::First => write!(f, "first"),
+            BiasLayout::None => write!(f, "none"),
+        }
+    }
+}
+
+impl From<&BiasLayout> for io::BiasPosition {
+    fn from(layout: &BiasLayout) -> Self {
+        match layout {
+            BiasLayout::Last => io::BiasPosition::Last,
+            BiasLayout::First => io::BiasPosition::First,
+            BiasLayout::None => io::BiasPosition::None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum BenchmarkOutput {
+    /// Human readable report
+    Text,
+    /// JSON array of {impl, mean_seconds, std_seconds, trials, feature_dim} records
+    Json,
+}
+
+impl Display for BenchmarkOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BenchmarkOutput::Text => write!(f, "text"),
+            BenchmarkOutput::Json => write!(f, "json"),
         }
     }
 }
 
+/// Layout of the prediction file
+#[derive(Clone, Debug, ValueEnum)]
+pub enum PredictionOutput {
+    /// One value per line
+    Text,
+    /// One {"index", "label", "score"} object per line; the score is the logistic
+    /// probability or the SVM decision value
+    Jsonl,
+}
+
+impl Display for PredictionOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PredictionOutput::Text => write!(f, "text"),
+            PredictionOutput::Jsonl => write!(f, "jsonl"),
+        }
+    }
+}
+
+/// Label for an SVM decision value of exactly zero
+#[derive(Clone, Debug, ValueEnum)]
+pub enum TieRule {
+    Negative,
+    Positive,
+    /// 0, marking the sample as undecided
+    Zero,
+}
+
+impl Display for TieRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TieRule::Negative => write!(f, "negative"),
+            TieRule::Positive => write!(f, "positive"),
+            TieRule::Zero => write!(f, "zero"),
+        }
+    }
+}
+
+impl From<&TieRule> for TieBreak {
+    fn from(rule: &TieRule) -> Self {
+        match rule {
+            TieRule::Negative => TieBreak::Negative,
+            TieRule::Positive => TieBreak::Positive,
+            TieRule::Zero => TieBreak::Zero,
+        }
+    }
+}
+
+/// How the members of an ensemble are combined
+#[derive(Clone, Debug, ValueEnum)]
+pub enum EnsembleMode {
+    /// Mean of the member probabilities (or SVM decision values)
+    Mean,
+    /// Majority vote of the member labels
+    Vote,
+}
+
+impl Display for EnsembleMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnsembleMode::Mean => write!(f, "mean"),
+            EnsembleMode::Vote => write!(f, "vote"),
+        }
+    }
+}
+
+impl From<&EnsembleMode> for Aggregation {
+    fn from(mode: &EnsembleMode) -> Self {
+        match mode {
+            EnsembleMode::Mean => Aggregation::MeanScore,
+            EnsembleMode::Vote => Aggregation::MajorityVote,
+        }
+    }
+}
+
+/// Instruction set used by logistic regression
+#[derive(ValueEnum, Clone, Debug)]
+pub enum SimdMode {
+    /// Best set supported by the running CPU
+    Auto,
+    /// Portable scalar loop
+    Scalar,
+    Sse,
+    Avx,
+    Avx2,
+    Avx512,
+    Neon,
+    /// Best set, but AVX2 instead of AVX-512. Some Xeons lower their clock under
+    /// 512-bit instructions, which can make AVX-512 the slower choice.
+    Avx2Prefer,
+}
+
+impl Display for SimdMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SimdMode::Auto => write!(f, "auto"),
+            SimdMode::Scalar => write!(f, "scalar"),
+            SimdMode::Sse => write!(f, "sse"),
+            SimdMode::Avx => write!(f, "avx"),
+            SimdMode::Avx2 => write!(f, "avx2"),
+            SimdMode::Avx512 => write!(f, "avx512"),
+            SimdMode::Neon => write!(f, "neon"),
+            SimdMode::Avx2Prefer => write!(f, "avx2-prefer"),
+        }
+    }
+}
+
+impl SimdMode {
+    /// Instruction set forced by this mode, or None to detect it at runtime
+    fn instruction_set(&self) -> Result<Option<SimdInstructionSet>> {
+        Ok(Some(match self {
+            SimdMode::Auto => return Ok(None),
+            SimdMode::Scalar => SimdInstructionSet::None,
+            SimdMode::Avx2Prefer => utils::best_simd().without_avx512(),
+            #[cfg(target_arch = "x86_64")]
+            SimdMode::Sse => SimdInstructionSet::SSE2,
+            #[cfg(target_arch = "x86_64")]
+            SimdMode::Avx => SimdInstructionSet::AVX,
+            #[cfg(target_arch = "x86_64")]
+            SimdMode::Avx2 => SimdInstructionSet::AVX2,
+            #[cfg(target_arch = "x86_64")]
+            SimdMode::Avx512 => SimdInstructionSet::AVX512,
+            #[cfg(target_arch = "aarch64")]
+            SimdMode::Neon => SimdInstructionSet::Neon,
+            #[allow(unreachable_patterns)]
+            other => bail!("--simd {} is not available on this architecture", other),
+        }))
+    }
+}
+
 /// InfernoInference
 #[derive(Parser)]
 #[command(author, about, version)]
 struct Args {
-    /// Binary file containing model parameters
-    #[arg(short, long, value_hint = ValueHint::FilePath)]
-    pub parameters: PathBuf,
+    /// Files containing model parameters, "-" for stdin. Models after the first,
+    /// e.g. challengers to the current model, are scored on the same data and
+    /// written as extra comma separated columns in --parameters order. Every
+    /// model must have the same feature dimension.
+    #[arg(short, long, num_args = 1.., required = true, value_hint = ValueHint::FilePath)]
+    pub parameters: Vec<PathBuf>,
+
+    /// Files containing input data matrices, "-" for stdin. Several files are
+    /// scored in order against one loaded model. Only one of --parameters,
+    /// --data and --labels can read from stdin in a run.
+    #[arg(short, long, num_args = 1.., required_unless_present_any = ["top_weights", "repl"], value_hint = ValueHint::FilePath)]
+    pub data: Vec<PathBuf>,
+
+    /// Format of the parameter and data files
+    #[arg(short, long, default_value_t = DataFormat::Binary)]
+    pub format: DataFormat,
+
+    /// Byte order of binary parameter, data and label files (.npy files carry their own)
+    #[arg(long, default_value_t = ByteOrder::Little)]
+    pub endian: ByteOrder,
+
+    /// Element type of binary data and label files, upcast to f32 before
+    /// inference. Parameter files are always f32.
+    #[arg(long, default_value_t = DataType::F32)]
+    pub dtype: DataType,
+
+    /// Where the bias sits in the parameter file (logistic and SVM)
+    #[arg(long, default_value_t = BiasLayout::Last, conflicts_with = "quantized")]
+    pub bias_position: BiasLayout,
 
-    /// Binary file containing input data matrix
-    #[arg(short, long, value_hint = ValueHint::FilePath)]
-    pub data: PathBuf,
+    /// Weights are an augmented vector whose last entry is the coefficient of an
+    /// implicit constant 1.0 feature, so inputs have one column fewer than the file
+    #[arg(long, default_value_t = false, conflicts_with_all = ["bias_position", "quantized"])]
+    pub augmented: bool,
+
+    /// Parameter file holds several models behind a [num_models, num_features] header,
+    /// combined by mean score or majority vote (logistic and SVM)
+    #[arg(long, conflicts_with = "quantized")]
+    pub ensemble: Option<EnsembleMode>,
+
+    /// Mixing weight per ensemble member, in file order, instead of equal
+    /// weights. They must sum to 1. SVM members mix decision values.
+    #[arg(long, num_args = 1.., value_name = "WEIGHT", requires = "ensemble")]
+    pub ensemble_weights: Option<Vec<f32>>,
+
+    /// Parameter file holds int8 quantized weights (logistic only)
+    #[arg(long, default_value_t = false)]
+    pub quantized: bool,
+
+    /// Fail instead of warning when parameters contain NaN or infinite values or a
+    /// data file holds no samples
+    #[arg(long, default_value_t = false)]
+    pub strict: bool,
+
+    /// Expected feature dimension, checked against the parameter and data files
+    #[arg(long)]
+    pub num_features: Option<NonZeroUsize>,
+
+    /// Order of the values in binary data files. Column-major files are read
+    /// whole, so they cannot be streamed with --chunk-size.
+    #[arg(long, default_value_t = DataLayout::Row, conflicts_with_all = ["chunk_size", "benchmarks"])]
+    pub data_layout: DataLayout,
+
+    /// Zero-pad the weights and every sample to a multiple of the SIMD lane
+    /// width (8 for AVX, 16 for AVX-512) when loading, so dot products have no
+    /// scalar tail. Predictions are unchanged.
+    #[arg(long, conflicts_with_all = ["chunk_size", "benchmarks"])]
+    pub pad_features: bool,
 
     /// Model type
     #[arg(short, long)]
     pub model: ModelType,
 
+    /// Write N seeded random samples to --data and matching linear model
+    /// parameters to --parameters, then exit. Needs --num-features.
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "num_features",
+        conflicts_with = "benchmarks"
+    )]
+    pub generate: Option<usize>,
+
+    /// Print the N features with the largest absolute weight and exit without
+    /// reading any data
+    #[arg(long, value_name = "N", conflicts_with_all = ["benchmarks", "generate"])]
+    pub top_weights: Option<NonZeroUsize>,
+
+    /// Load and check the parameter, data and label files without scoring them,
+    /// then print a summary. Every problem --strict would reject is an error.
+    #[arg(long, conflicts_with_all = ["benchmarks", "generate", "top_weights"])]
+    pub validate_only: bool,
+
+    /// Score comma separated samples typed on stdin, one per line, printing each
+    /// prediction and its score until EOF
+    #[arg(long, conflicts_with_all = ["data", "labels", "benchmarks", "generate", "top_weights", "validate_only", "pad_features"])]
+    pub repl: bool,
+
+    /// Seed for --generate; the same seed always writes the same files
+    #[arg(long, default_value_t = 0, requires = "generate")]
+    pub seed: u64,
+
     /// Run benchmarks instead of inference
     #[arg(short, long, default_value_t = false)]
     pub benchmarks: bool,
 
     #[arg(short, long, default_value_t = 50)]
     pub trials: usize,
+
+    /// Untimed passes over the data before timing each implementation
+    #[arg(long, default_value_t = 5)]
+    pub warmup: usize,
+
+    /// Also report per-sample latency percentiles (p50/p90/p99) when benchmarking
+    #[arg(long, default_value_t = false, requires = "benchmarks")]
+    pub latency: bool,
+
+    /// Run over the benchmark data N times in every timed trial, so a small data
+    /// file gives stable timings without generating a larger one
+    #[arg(long, value_name = "N", default_value_t = NonZeroUsize::MIN, requires = "benchmarks")]
+    pub repeat_data: NonZeroUsize,
+
+    /// Stop starting new trials of an implementation once its timed trials
+    /// have run this many seconds; at least 3 trials always complete
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds, requires = "benchmarks")]
+    pub max_seconds: Option<Duration>,
+
+    /// Benchmark report format
+    #[arg(long, default_value_t = BenchmarkOutput::Text)]
+    pub benchmark_format: BenchmarkOutput,
+
+    /// Write the JSON benchmark report to this file instead of stdout
+    #[arg(long, requires = "benchmarks", value_hint = ValueHint::FilePath)]
+    pub benchmark_output: Option<PathBuf>,
+
+    /// Write logistic probabilities, SVM decision scores (calibrated with --platt),
+    /// naive Bayes class posteriors or KNN vote shares instead of labels
+    #[arg(long, default_value_t = false)]
+    pub output_probabilities: bool,
+
+    /// Write the raw logistic logit w . x + b of each sample instead of labels
+    #[arg(long, default_value_t = false, conflicts_with_all = ["output_probabilities", "bins", "verify"])]
+    pub output_logits: bool,
+
+    /// Probability cutoff for logistic labels; values outside [0, 1] are rejected rather than clamped
+    #[arg(long, default_value_t = 0.5, value_parser = parse_threshold)]
+    pub threshold: f32,
+
+    /// Comma separated probability cut points, e.g. 0.3,0.7, sorted and within [0, 1].
+    /// Logistic predictions become the index of their bin (here 0, 1 or 2) instead
+    /// of a 0/1 label; an empty list keeps the single --threshold.
+    #[arg(long, value_parser = parse_bins, conflicts_with = "output_probabilities")]
+    pub bins: Option<CutPoints>,
+
+    /// Prediction file layout; jsonl tags each sample with its row index in its
+    /// data file (logistic regression and SVM only)
+    #[arg(long, default_value_t = PredictionOutput::Text, conflicts_with_all = ["output_probabilities", "output_logits", "bins"])]
+    pub output_format: PredictionOutput,
+
+    /// Stream the data file, holding at most this many samples in memory (binary only)
+    #[arg(long, conflicts_with = "benchmarks")]
+    pub chunk_size: Option<NonZeroUsize>,
+
+    /// Where to write predictions, "-" for stdout. Predictions for several data
+    /// files are concatenated in the order the files were given.
+    #[arg(short, long, default_value = "output", value_hint = ValueHint::FilePath)]
+    pub output: PathBuf,
+
+    /// Write the predictions for each data file to DIR/<data file name>.out instead
+    #[arg(long, value_name = "DIR", conflicts_with = "output", value_hint = ValueHint::DirPath)]
+    pub output_dir: Option<PathBuf>,
+
+    /// Ground-truth labels in the same format as the data, one per sample (0/1 for
+    /// logistic, -1/1 for SVM, class index for naive Bayes and KNN). Prints
+    /// accuracy, precision, recall and a confusion matrix after inference.
+    #[arg(long, conflicts_with = "benchmarks", value_hint = ValueHint::FilePath)]
+    pub labels: Option<PathBuf>,
+
+    /// Clamp every logistic input feature to [MIN, MAX] before the dot product
+    #[arg(long, num_args = 2, value_names = ["MIN", "MAX"], allow_negative_numbers = true)]
+    pub clip: Option<Vec<f32>>,
+
+    /// Clamp the logistic logit to [MIN, MAX] before the sigmoid
+    #[arg(long, num_args = 2, value_names = ["MIN", "MAX"], allow_negative_numbers = true)]
+    pub clip_logit: Option<Vec<f32>>,
+
+    /// Platt scaling parameters for an SVM, so --output-probabilities writes
+    /// 1 / (1 + exp(A * score + B)) instead of raw decision values
+    #[arg(long, num_args = 2, value_names = ["A", "B"], allow_negative_numbers = true)]
+    pub platt: Option<Vec<f32>>,
+
+    /// SVM label for a decision value of exactly zero: -1 (the default), 1 or 0.
+    /// A 0 label counts as the negative class in --labels evaluation.
+    #[arg(long)]
+    pub tie_break: Option<TieRule>,
+
+    /// Also score logistic regression with the scalar strategy, report the largest
+    /// probability difference and fail if it exceeds TOLERANCE
+    #[arg(long, value_name = "TOLERANCE", num_args = 0..=1, default_missing_value = "1e-5", conflicts_with = "benchmarks")]
+    pub verify: Option<f32>,
+
+    /// Force the instruction set used by logistic regression, failing if the CPU lacks it
+    #[arg(long, default_value_t = SimdMode::Auto)]
+    pub simd: SimdMode,
+
+    /// Only log errors, dropping the model, data and system information
+    #[arg(short, long, default_value_t = false)]
+    pub quiet: bool,
+
+    /// Print a one-line JSON summary {num_samples, num_features, model} after inference
+    #[arg(long, default_value_t = false, conflicts_with = "benchmarks")]
+    pub summary: bool,
+
+    /// Run batch inference on this many threads (0 uses all cores)
+    #[cfg(feature = "rayon")]
+    #[arg(long)]
+    pub threads: Option<usize>,
+}
+
+/// Sorted probability cut points given to --bins
+#[derive(Clone, Debug, PartialEq)]
+pub struct CutPoints(Vec<f32>);
+
+impl CutPoints {
+    /// Index of the bin holding `prob`. A probability equal to a cut point falls in
+    /// the lower bin, matching `prob > threshold` for a single cut.
+    fn bin(&self, prob: f32) -> usize {
+        self.0.partition_point(|&cut| cut < prob)
+    }
+}
+
+impl Args {
+    /// Cut points from --bins, or None when the single threshold applies
+    fn cut_points(&self) -> Option<&CutPoints> {
+        self.bins.as_ref().filter(|bins| !bins.0.is_empty())
+    }
+
+    /// True when problems that only warn by default should fail the run
+    fn strict_checks(&self) -> bool {
+        self.strict || self.validate_only
+    }
+
+    fn bias_layout(&self) -> io::BiasPosition {
+        // w . [x, 1] = w[..n] . x + w[n], which is a trailing bias
+        if self.augmented {
+            return io::BiasPosition::Last;
+        }
+        (&self.bias_position).into()
+    }
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    // Diagnostics go to stderr at info level unless RUST_LOG or --quiet says otherwise
+    let mut logger =
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"));
+    if args.quiet {
+        logger.filter_level(log::LevelFilter::Error);
+    }
+    logger.init();
+    fast_inference::utils::print_system_info();
 
-    println!("\nModel Type: {}", args.model);
-    let mut params_file = BufReader::new(File::open(&args.parameters)?);
-    let mut params_bytes = Vec::new();
-    params_file.read_to_end(&mut params_bytes)?;
-    // bytes -> f32 array
-    let params: Vec<f32> = params_bytes
-        .chunks(4)
-        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
-        .collect();
-
-    // create output file to write results to when not benchmarking
-    let mut out_file = File::create("output")?;
-
-    // Split into weights and bias
-    let _bias = params[params.len() - 1];
-    let weights = params[..params.len() - 1].to_vec();
-
-    // Read data matrix
-    let mut data_file = BufReader::new(File::open(&args.data)?);
-    let mut data_bytes = Vec::new();
-    data_file.read_to_end(&mut data_bytes)?;
-
-    // bytes -> f32 matrix
-    let data: Vec<f32> = data_bytes
-        .chunks(4)
-        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
-        .collect();
-
-    // Process based on model type
-    let num_features = weights.len();
-    let num_samples = data.len() / num_features;
-    println!("Number of samples: {}", num_samples);
-    println!("Feature dimension: {}", num_features);
+    // stdin can only be consumed once
+    let stdin = Path::new("-");
+    let inputs = args
+        .parameters
+        .iter()
+        .chain(&args.data)
+        .map(PathBuf::as_path)
+        .chain(args.labels.as_deref());
+    if inputs.filter(|&path| path == stdin).count() > 1 {
+        bail!("Only one of --parameters, --data and --labels can read from stdin");
+    }
+
+    if let Some(num_samples) = args.generate {
+        return generate(&args, num_samples);
+    }
+
+    #[cfg(feature = "rayon")]
+    if let Some(threads) = args.threads {
+        let pool = InferencePool::new(threads).context("Failed to build the --threads pool")?;
+        let _ = POOL.set(pool);
+    }
+
+    let parameters = args.parameters[0].as_path();
+    if args.parameters.len() > 1
+        && (args.benchmarks || args.validate_only || args.repl || args.top_weights.is_some())
+    {
+        bail!("Several --parameters files are only supported when writing predictions");
+    }
+    // Each model must write one value per sample to fill its column, and
+    // --pad-features would only widen the first model
+    let single_model_only = args.pad_features
+        || matches!(args.output_format, PredictionOutput::Jsonl)
+        || (args.output_probabilities
+            && matches!(args.model, ModelType::NaiveBayes | ModelType::Knn));
+    if args.parameters.len() > 1 && single_model_only {
+        bail!("--pad-features, --output-format jsonl and class probabilities take a single --parameters file");
+    }
+
+    info!("Model Type: {}", args.model);
+    let (model, header) = load_model(&args, parameters)?;
+    if let Model::Logistic(model) = &model {
+        info!(
+            "Instruction set: {:?}",
+            model.strategy().inner.instruction_set()
+        );
+    }
+    let num_features = model.num_features();
+    if num_features == 0 {
+        bail!(
+            "{} describes a model with no features",
+            parameters.display()
+        );
+    }
+    check_header(parameters, header, num_features)?;
+    if let Some(expected) = args.num_features {
+        if expected.get() != num_features {
+            bail!(
+                "--num-features is {} but {} describes {} features",
+                expected,
+                parameters.display(),
+                num_features
+            );
+        }
+    }
+
+    if let Some(count) = args.top_weights {
+        let weights = match &model {
+            Model::Logistic(model) => model.weights(),
+            Model::Svm(model) => model.weights(),
+            _ => bail!("--top-weights only applies to a single logistic or SVM model"),
+        };
+        print_top_weights(&args, weights, count.get());
+        return Ok(());
+    }
+
+    if matches!(args.dtype, DataType::F16) && !matches!(args.format, DataFormat::Binary) {
+        bail!("--dtype f16 only applies to binary data files");
+    }
+    if matches!(args.data_layout, DataLayout::Col) && !matches!(args.format, DataFormat::Binary) {
+        bail!("--data-layout col only applies to binary data files");
+    }
 
     // If benchmark flag is present, run benchmarks
     if args.benchmarks {
-        #[allow(unreachable_code)]
-        match args.model {
-            ModelType::Logistic => {
-                #[cfg(target_arch = "x86_64")]
-                {
-                    benchmarks::logistic::run_benchmarks(
-                        &args.parameters,
-                        &args.data,
-                        args.trials,
-                    )?;
-                    return Ok(());
-                }
+        let [data_path] = args.data.as_slice() else {
+            bail!("Benchmarks take a single data file");
+        };
+        if matches!(args.dtype, DataType::F16) {
+            bail!("Benchmarks only read f32 data files");
+        }
+        if !matches!(args.format, DataFormat::Binary) {
+            bail!("Benchmarks only support binary input files");
+        }
+        if args.quantized || args.ensemble.is_some() {
+            bail!("Benchmarks do not support quantized or ensemble parameters");
+        }
+        if matches!(args.endian, ByteOrder::Big) {
+            bail!("Benchmarks only read little-endian files");
+        }
+        if parameters == stdin || data_path == stdin {
+            bail!("Benchmarks re-read the parameter and data files, so they cannot use stdin");
+        }
+        let json = matches!(args.benchmark_format, BenchmarkOutput::Json);
+        if args.benchmark_output.is_some() && !json {
+            bail!("--benchmark-output requires --benchmark-format json");
+        }
 
-                #[cfg(target_arch = "aarch64")]
-                {
-                    let model_neon = LogisticRegression::new(weights.clone(), bias, NEON);
-                    let num_trials = matches
-                        .get_one::<String>("trials")
-                        .unwrap()
-                        .parse::<usize>()
-                        .expect("Trials must be a positive integer");
-
-                    println!("\nBenchmarking ARM NEON implementation:");
-                    let mut times_neon = Vec::with_capacity(num_trials);
-
-                    for _ in 0..num_trials {
-                        let start = Instant::now();
-                        for chunk in data.chunks(num_features) {
-                            let _ = model_neon.predict(chunk);
-                        }
-                        times_neon.push(start.elapsed().as_secs_f64());
-                    }
+        let data = load_data(
+            data_path,
+            &args.format,
+            io::DType::F32,
+            (&args.endian).into(),
+            &args.data_layout,
+            num_features,
+        )?;
+        info!("Number of samples: {}", data.num_rows());
+        info!("Feature dimension: {}", num_features);
+        if args.repeat_data.get() > 1 {
+            info!(
+                "Samples per trial: {} ({} repeats)",
+                data.num_rows() * args.repeat_data.get(),
+                args.repeat_data
+            );
+        }
+        let config = BenchmarkConfig {
+            num_trials: args.trials,
+            warmup: args.warmup,
+            latency: args.latency,
+            repeat_data: args.repeat_data.get(),
+            max_duration: args.max_seconds,
+            format: if json {
+                BenchmarkFormat::Json
+            } else {
+                BenchmarkFormat::Text
+            },
+        };
+        let mut records = match args.model {
+            ModelType::Logistic => benchmarks::logistic::run_benchmarks(
+                parameters,
+                data_path,
+                args.bias_layout(),
+                &config,
+            )?,
+            ModelType::Svm => {
+                benchmarks::svm::run_benchmarks(parameters, data_path, args.bias_layout(), &config)?
+            }
+            ModelType::NaiveBayes => bail!("Benchmarks are not available for naive Bayes"),
+            ModelType::Knn => bail!("Benchmarks are not available for KNN"),
+        };
+        records.extend(benchmarks::kernels::run_benchmarks(&config));
+
+        if json {
+            let report = benchmarks::records_to_json(&records);
+            match &args.benchmark_output {
+                Some(path) => std::fs::write(path, report).with_context(|| {
+                    format!("Failed to write benchmark report to {}", path.display())
+                })?,
+                None => print!("{}", report),
+            }
+        }
+        return Ok(());
+    }
+
+    if args.validate_only {
+        let num_samples = validate_files(&args, &model, num_features)?;
+        print_result(
+            &args,
+            format!(
+                "Valid: {} data file(s), {} samples, {} features",
+                args.data.len(),
+                num_samples,
+                num_features
+            ),
+        );
+        return Ok(());
+    }
+
+    if args.repl {
+        if parameters == stdin {
+            bail!("--repl reads samples from stdin, so the parameters cannot come from it");
+        }
+        return run_repl(&model, &args);
+    }
+
+    // Padded once here; predict_file pads each data file to the same width
+    let model = match model {
+        Model::Logistic(model) if args.pad_features => {
+            let lanes = model.strategy().inner.lanes();
+            Model::Logistic(model.pad_to_lanes(lanes))
+        }
+        model => model,
+    };
+    if model.num_features() != num_features {
+        info!("Padded feature dimension: {}", model.num_features());
+    }
+    let models: Vec<Model> = std::iter::once(Ok(model))
+        .chain(
+            args.parameters[1..]
+                .iter()
+                .map(|path| load_challenger(&args, path, num_features)),
+        )
+        .collect::<Result<_>>()?;
+    if models.len() > 1 {
+        info!("Models: {}, one output column each", models.len());
+    }
+    let model = &models[0];
+
+    if args.chunk_size.is_some() && !matches!(args.format, DataFormat::Binary) {
+        bail!("Streaming only supports binary data files");
+    }
+    info!("Feature dimension: {}", num_features);
+    if let Some(chunk_size) = args.chunk_size {
+        info!("Samples per chunk: {}", chunk_size);
+    }
+
+    // create output to write results to, shared by every data file unless --output-dir is set
+    let output_paths = output_paths(&args)?;
+    let mut shared_output = match args.output_dir {
+        None => Some(open_output(&args.output)?),
+        Some(_) => None,
+    };
+    let mut evaluation = load_labels(&args, model)?;
+    let mut verification = scalar_reference(model, &args);
+    let progress = Progress::new(&args, num_features);
+    let mut num_samples = 0;
+    for (i, data_path) in args.data.iter().enumerate() {
+        let mut own_output;
+        let out_file: &mut dyn Write = match &mut shared_output {
+            Some(out_file) => out_file.as_mut(),
+            None => {
+                let path = &output_paths[i];
+                own_output = open_output(path)
+                    .with_context(|| format!("Failed to create output file {}", path.display()))?;
+                own_output.as_mut()
+            }
+        };
+        let file_samples = predict_file(
+            &args,
+            &models,
+            num_features,
+            data_path,
+            out_file,
+            evaluation.as_mut(),
+            verification.as_mut(),
+            &progress,
+        )?;
+        out_file.flush()?;
+        check_samples(data_path, file_samples, args.strict_checks())?;
+        if args.data.len() > 1 {
+            info!("{}: {} samples", data_path.display(), file_samples);
+        }
+        num_samples += file_samples;
+    }
+    progress.finish();
+    info!("Number of samples: {}", num_samples);
+
+    if let Some(evaluation) = evaluation {
+        report_evaluation(&args, evaluation)?;
+    }
+    if let Some(verification) = verification {
+        report_verification(&args, verification)?;
+    }
+    if args.summary {
+        print_summary(&args, num_samples, num_features);
+    }
+
+    Ok(())
+}
+
+/// Writes predictions for one data file, streaming it in blocks when --chunk-size
+/// is set. `num_features` is the width of the file, which --pad-features widens
+/// to the model's. Returns the number of samples scored.
+#[allow(clippy::too_many_arguments)]
+fn predict_file(
+    args: &Args,
+    models: &[Model],
+    num_features: usize,
+    path: &Path,
+    out_file: &mut dyn Write,
+    mut evaluation: Option<&mut Evaluation>,
+    mut verification: Option<&mut Verification>,
+    progress: &Progress,
+) -> Result<usize> {
+    let Some(chunk_size) = args.chunk_size else {
+        let data = load_data(
+            path,
+            &args.format,
+            (&args.dtype).into(),
+            (&args.endian).into(),
+            &args.data_layout,
+            num_features,
+        )?
+        .pad_cols(models[0].num_features());
+        // Score in blocks only when there is a progress bar to update
+        let block_rows = progress.samples_per_update().unwrap_or(data.num_rows());
+        for (i, block) in data.row_blocks(block_rows).enumerate() {
+            write_columns(
+                args,
+                models,
+                block,
+                data.num_cols(),
+                i * block_rows,
+                out_file,
+                evaluation.as_deref_mut(),
+                verification.as_deref_mut(),
+            )?;
+            progress.inc(block.len() / data.num_cols());
+        }
+        return Ok(data.num_rows());
+    };
+
+    // Stream the data file in bounded blocks instead of reading it whole
+    let read_context = || format!("Failed to read data from {}", path.display());
+    let mut num_samples = 0;
+    let blocks = io::stream_f32_binary(path, num_features, chunk_size.get(), (&args.endian).into())
+        .with_context(read_context)?
+        .dtype((&args.dtype).into());
+    for block in blocks {
+        let block = block.with_context(read_context)?;
+        write_columns(
+            args,
+            models,
+            &block,
+            num_features,
+            num_samples,
+            out_file,
+            evaluation.as_deref_mut(),
+            verification.as_deref_mut(),
+        )?;
+        num_samples += block.len() / num_features;
+        progress.inc(block.len() / num_features);
+    }
+    Ok(num_samples)
+}
+
+/// Writes the predictions of every model for one block, one comma separated
+/// column per model. Only the first model records into `evaluation` and
+/// `verification`.
+#[allow(clippy::too_many_arguments)]
+fn write_columns(
+    args: &Args,
+    models: &[Model],
+    data: &[f32],
+    num_features: usize,
+    first_index: usize,
+    out_file: &mut dyn Write,
+    mut evaluation: Option<&mut Evaluation>,
+    mut verification: Option<&mut Verification>,
+) -> Result<()> {
+    if let [model] = models {
+        return model.write_predictions(
+            args,
+            data,
+            num_features,
+            first_index,
+            out_file,
+            evaluation,
+            verification,
+        );
+    }
+    let mut columns = Vec::with_capacity(models.len());
+    for model in models {
+        let mut column = Vec::new();
+        model.write_predictions(
+            args,
+            data,
+            num_features,
+            first_index,
+            &mut column,
+            evaluation.take(),
+            verification.take(),
+        )?;
+        columns.push(String::from_utf8(column)?);
+    }
+    let mut columns: Vec<_> = columns.iter().map(|column| column.lines()).collect();
+    for _ in 0..data.len() / num_features {
+        let row: Vec<&str> = columns
+            .iter_mut()
+            .map(|lines| lines.next().unwrap_or_default())
+            .collect();
+        writeln!(out_file, "{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+// Samples scored between progress bar updates when a whole data file is in memory
+#[cfg(feature = "progress")]
+const PROGRESS_BLOCK_SAMPLES: usize = 1 << 16;
+
+/// Progress bar over every data file, drawn on stderr only when it is a terminal
+/// and --quiet is not set. Does nothing without the `progress` feature.
+#[cfg(feature = "progress")]
+struct Progress(Option<indicatif::ProgressBar>);
+
+#[cfg(not(feature = "progress"))]
+struct Progress;
 
-                    let mean = times_neon.iter().sum::<f64>() / times_neon.len() as f64;
-                    let variance = times_neon.iter().map(|x| (x - mean).powi(2)).sum::<f64>()
-                        / times_neon.len() as f64;
-                    let std_dev = variance.sqrt();
+#[cfg(feature = "progress")]
+impl Progress {
+    fn new(args: &Args, num_features: usize) -> Self {
+        use indicatif::{ProgressBar, ProgressStyle};
+        use std::io::IsTerminal;
 
-                    println!("Mean Time: {:.2e} ± {:.2e} seconds", mean, std_dev);
-                    return Ok(());
+        if args.quiet || !std::io::stderr().is_terminal() {
+            return Self(None);
+        }
+        let bar = match expected_samples(args, num_features) {
+            Some(total) => ProgressBar::new(total).with_style(
+                ProgressStyle::with_template(
+                    "[{elapsed_precise}] {bar:40} {pos}/{len} samples ({per_sec}, ETA {eta})",
+                )
+                .unwrap(),
+            ),
+            // Sizes are only known up front for binary files
+            None => ProgressBar::new_spinner().with_style(
+                ProgressStyle::with_template(
+                    "{spinner} [{elapsed_precise}] {pos} samples ({per_sec})",
+                )
+                .unwrap(),
+            ),
+        };
+        Self(Some(bar))
+    }
+
+    fn samples_per_update(&self) -> Option<usize> {
+        self.0.as_ref().map(|_| PROGRESS_BLOCK_SAMPLES)
+    }
+
+    fn inc(&self, samples: usize) {
+        if let Some(bar) = &self.0 {
+            bar.inc(samples as u64);
+        }
+    }
+
+    fn finish(&self) {
+        if let Some(bar) = &self.0 {
+            bar.finish();
+        }
+    }
+}
+
+#[cfg(not(feature = "progress"))]
+impl Progress {
+    fn new(_args: &Args, _num_features: usize) -> Self {
+        Self
+    }
+
+    fn samples_per_update(&self) -> Option<usize> {
+        None
+    }
+
+    fn inc(&self, _samples: usize) {}
+
+    fn finish(&self) {}
+}
+
+/// Total samples across the data files, from their sizes. None unless every file
+/// is an uncompressed binary file on disk.
+#[cfg(feature = "progress")]
+fn expected_samples(args: &Args, num_features: usize) -> Option<u64> {
+    if !matches!(args.format, DataFormat::Binary) {
+        return None;
+    }
+    let sample_bytes = (num_features * io::DType::from(&args.dtype).size()) as u64;
+    let mut total = 0;
+    for path in &args.data {
+        if path == Path::new("-") || io::is_gzip(path) {
+            return None;
+        }
+        total += std::fs::metadata(path).ok()?.len() / sample_bytes;
+    }
+    Some(total)
+}
+
+/// With --output-dir, names the output of each data file after the file itself.
+/// Empty when every data file writes to --output.
+fn output_paths(args: &Args) -> Result<Vec<PathBuf>> {
+    let Some(dir) = &args.output_dir else {
+        return Ok(Vec::new());
+    };
+    let mut paths: Vec<PathBuf> = Vec::with_capacity(args.data.len());
+    for data_path in &args.data {
+        let name = match data_path.file_name() {
+            Some(name) if data_path != Path::new("-") => name.to_os_string(),
+            _ => "stdin".into(),
+        };
+        let mut file_name = name;
+        file_name.push(".out");
+        let path = dir.join(file_name);
+        if paths.contains(&path) {
+            bail!(
+                "Several data files would write to {}; give them distinct names",
+                path.display()
+            );
+        }
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// Model selected on the command line
+enum Model {
+    Logistic(LogisticRegression<f32, Clip<Auto>>),
+    Svm(SupportVectorMachine<f32, SVMSequential>),
+    NaiveBayes(GaussianNaiveBayes<f32>),
+    Knn(Knn),
+    /// Logistic or SVM members, depending on `svm`
+    Ensemble {
+        model: Ensemble,
+        svm: bool,
+        tie_break: TieBreak,
+    },
+}
+
+/// Writes seeded synthetic parameter and data files for a linear model
+fn generate(args: &Args, num_samples: usize) -> Result<()> {
+    if !matches!(args.model, ModelType::Logistic | ModelType::Svm) {
+        bail!(
+            "--generate writes linear model parameters, not {}",
+            args.model
+        );
+    }
+    let [parameters] = args.parameters.as_slice() else {
+        bail!("--generate writes exactly one --parameters file");
+    };
+    let [data] = args.data.as_slice() else {
+        bail!("--generate writes exactly one --data file");
+    };
+    let num_features = args.num_features.map_or(0, NonZeroUsize::get);
+    utils::synthetic::generate(parameters, data, num_features, num_samples, args.seed)
+        .context("Failed to write generated files")?;
+    info!(
+        "Generated {} samples with {} features (seed {})",
+        num_samples, num_features, args.seed
+    );
+    Ok(())
+}
+
+/// Loads the parameter file into the model selected on the command line, also
+/// returning the file's header if it has one
+fn load_model(args: &Args, path: &Path) -> Result<(Model, Option<ParamHeader>)> {
+    if args.quantized && !matches!(args.model, ModelType::Logistic) {
+        bail!("Quantized parameters are only supported for logistic regression");
+    }
+    if args.quantized && matches!(args.endian, ByteOrder::Big) {
+        bail!("Quantized parameter files are always little-endian");
+    }
+    if !matches!(args.model, ModelType::Logistic)
+        && !matches!(args.simd, SimdMode::Auto | SimdMode::Scalar)
+    {
+        bail!("--simd only applies to logistic regression");
+    }
+    if !matches!(args.model, ModelType::Logistic)
+        && (args.clip.is_some() || args.clip_logit.is_some())
+    {
+        bail!("--clip and --clip-logit only apply to logistic regression");
+    }
+    if args.platt.is_some() && (!matches!(args.model, ModelType::Svm) || args.ensemble.is_some()) {
+        bail!("--platt only applies to a single SVM model");
+    }
+    if args.cut_points().is_some()
+        && (!matches!(args.model, ModelType::Logistic) || args.ensemble.is_some())
+    {
+        bail!("--bins only applies to a single logistic regression model");
+    }
+    if args.output_logits && (!matches!(args.model, ModelType::Logistic) || args.ensemble.is_some())
+    {
+        bail!("--output-logits only applies to a single logistic regression model");
+    }
+    if matches!(args.output_format, PredictionOutput::Jsonl)
+        && (!matches!(args.model, ModelType::Logistic | ModelType::Svm) || args.ensemble.is_some())
+    {
+        bail!("--output-format jsonl only applies to a single logistic regression or SVM model");
+    }
+    if args.pad_features && (!matches!(args.model, ModelType::Logistic) || args.ensemble.is_some())
+    {
+        bail!("--pad-features only applies to a single logistic regression model");
+    }
+    if args.verify.is_some()
+        && (!matches!(args.model, ModelType::Logistic) || args.ensemble.is_some())
+    {
+        bail!("--verify only applies to a single logistic regression model");
+    }
+    if let Some(tolerance) = args.verify {
+        if tolerance.is_nan() || tolerance < 0.0 {
+            bail!("--verify tolerance must be non-negative, got {}", tolerance);
+        }
+    }
+    if args.tie_break.is_some() && !matches!(args.model, ModelType::Svm) {
+        bail!("--tie-break only applies to SVM models");
+    }
+    let tie_break = args
+        .tie_break
+        .as_ref()
+        .map_or(TieBreak::default(), Into::into);
+    let inner = match args.simd.instruction_set()? {
+        None => Auto::new(),
+        Some(set) => Auto::try_from_instruction_set(set)
+            .with_context(|| format!("Cannot use --simd {}", args.simd))?,
+    };
+    let strategy = Clip {
+        inner,
+        input: clip_range(&args.clip, "--clip")?,
+        logit: clip_range(&args.clip_logit, "--clip-logit")?,
+    };
+
+    if let Some(mode) = &args.ensemble {
+        if let ModelType::NaiveBayes | ModelType::Knn = args.model {
+            bail!("--ensemble only supports logistic regression and SVM");
+        }
+        let (params, header) = read_param_matrix(args, path)?;
+        let bias_position = args.bias_layout();
+        let aggregation = mode.into();
+        let svm = matches!(args.model, ModelType::Svm);
+        let model = if svm {
+            Ensemble::from_params(&params, bias_position, aggregation, |w, b| {
+                SupportVectorMachine::new(w, b, SVMSequential).with_tie_break(tie_break)
+            })
+        } else {
+            Ensemble::from_params(&params, bias_position, aggregation, |w, b| {
+                LogisticRegression::new(w, b, strategy)
+            })
+        }
+        .with_context(|| format!("Invalid parameter file {}", path.display()))?;
+        let model = match &args.ensemble_weights {
+            Some(weights) => model
+                .with_mixing_weights(weights.clone())
+                .context("Invalid --ensemble-weights")?,
+            None => model,
+        };
+        return Ok((
+            Model::Ensemble {
+                model,
+                svm,
+                tie_break,
+            },
+            header,
+        ));
+    }
+
+    let (weights, bias, header) = match args.model {
+        ModelType::NaiveBayes => {
+            let (params, header) = read_param_matrix(args, path)?;
+            let model = GaussianNaiveBayes::from_params(&params)
+                .with_context(|| format!("Invalid parameter file {}", path.display()))?;
+            return Ok((Model::NaiveBayes(model), header));
+        }
+        ModelType::Knn => {
+            let (params, header) = read_param_matrix(args, path)?;
+            let model = Knn::from_params(&params)
+                .with_context(|| format!("Invalid parameter file {}", path.display()))?;
+            return Ok((Model::Knn(model), header));
+        }
+        _ if args.quantized => {
+            let model = QuantizedLogisticRegression::from_param_file(path).with_context(|| {
+                format!(
+                    "Failed to read quantized parameters from {}",
+                    path.display()
+                )
+            })?;
+            let weights = model.dequantize();
+            check_params(
+                validate::scan_params(&weights, model.bias()),
+                args.strict_checks(),
+            )?;
+            (weights, model.bias(), None)
+        }
+        _ if matches!(args.format, DataFormat::Binary) => {
+            let (weights, bias, header) =
+                io::read_params_with_header(path, args.bias_layout(), (&args.endian).into())
+                    .with_context(|| {
+                        format!("Failed to read parameters from {}", path.display())
+                    })?;
+            check_declared_model(args, path, header)?;
+            // Report indices in file order, where a leading bias comes first
+            let report = match args.bias_layout() {
+                io::BiasPosition::First => {
+                    validate::scan_values(std::iter::once(&bias).chain(&weights))
                 }
+                _ => validate::scan_params(&weights, bias),
+            };
+            check_params(report, args.strict_checks())?;
+            (weights, bias, header)
+        }
+        _ => {
+            let (params, header) = read_param_matrix(args, path)?;
+            let (weights, bias) = io::split_bias(params, args.bias_layout())
+                .with_context(|| format!("Invalid parameter file {}", path.display()))?;
+            (weights, bias, header)
+        }
+    };
 
-                bail!("Unknown/unsupported arch");
+    if let ModelType::Svm = args.model {
+        let mut model =
+            SupportVectorMachine::new(weights, bias, SVMSequential).with_tie_break(tie_break);
+        match args.platt.as_deref() {
+            None => {}
+            Some(&[a, b]) if a.is_finite() && b.is_finite() => {
+                model = model.with_platt_params(a, b);
             }
+            Some(values) => bail!("--platt needs finite A and B, got {:?}", values),
+        }
+        return Ok((Model::Svm(model), header));
+    }
 
-            ModelType::Svm => {
-                bail!("Benchmarking not yet implemented for SVM");
+    let model = LogisticRegression::new(weights, bias, strategy);
+    Ok((Model::Logistic(model), header))
+}
+
+impl Model {
+    fn num_features(&self) -> usize {
+        match self {
+            Model::Logistic(model) => model.num_features(),
+            Model::Svm(model) => model.num_features(),
+            Model::NaiveBayes(model) => model.num_features(),
+            Model::Knn(model) => model.num_features(),
+            Model::Ensemble { model, .. } => model.num_features(),
+        }
+    }
+
+    fn num_classes(&self) -> usize {
+        match self {
+            Model::Logistic(_) | Model::Svm(_) | Model::Ensemble { .. } => 2,
+            Model::NaiveBayes(model) => model.num_classes(),
+            Model::Knn(model) => model.num_classes(),
+        }
+    }
+
+    /// Maps a ground-truth label to the class index predictions are counted under
+    fn label_class(&self, label: f32) -> Option<usize> {
+        match self {
+            Model::Logistic(_) | Model::Ensemble { svm: false, .. }
+                if label == 0.0 || label == 1.0 =>
+            {
+                Some(label as usize)
+            }
+            // 0 is accepted as the negative class too, as many exporters write 0/1
+            Model::Svm(_) | Model::Ensemble { svm: true, .. } if label == 1.0 => Some(1),
+            Model::Svm(_) | Model::Ensemble { svm: true, .. } if label == -1.0 || label == 0.0 => {
+                Some(0)
+            }
+            Model::NaiveBayes(_) | Model::Knn(_)
+                if label >= 0.0 && label.fract() == 0.0 && label < self.num_classes() as f32 =>
+            {
+                Some(label as usize)
+            }
+            _ => None,
+        }
+    }
+
+    /// Scores a block of samples and writes one prediction per line, recording
+    /// each predicted class in `evaluation` when labels were provided and
+    /// comparing logistic probabilities with `verification` under --verify.
+    /// `first_index` is the row of the block's first sample in its data file.
+    #[allow(clippy::too_many_arguments)]
+    fn write_predictions(
+        &self,
+        args: &Args,
+        data: &[f32],
+        num_features: usize,
+        first_index: usize,
+        out_file: &mut dyn Write,
+        evaluation: Option<&mut Evaluation>,
+        verification: Option<&mut Verification>,
+    ) -> Result<()> {
+        match self {
+            Model::Logistic(model) if args.output_logits => {
+                #[cfg(feature = "rayon")]
+                let logits = match POOL.get() {
+                    Some(pool) => pool.install(|| model.logit_batch_parallel(data, num_features)),
+                    None => model.logit_batch(data, num_features),
+                };
+                #[cfg(not(feature = "rayon"))]
+                let logits = model.logit_batch(data, num_features);
+                if let Some(evaluation) = evaluation {
+                    for &logit in &logits {
+                        evaluation.record(usize::from(sigmoid(logit) > args.threshold))?;
+                    }
+                }
+                for logit in logits {
+                    out_file.write_all(format!("{}\n", logit).as_bytes())?;
+                }
+            }
+            Model::Logistic(model) => {
+                #[cfg(feature = "rayon")]
+                let probs = match POOL.get() {
+                    Some(pool) => pool.install(|| model.predict_batch_parallel(data, num_features)),
+                    None => model.predict_batch(data, num_features),
+                };
+                #[cfg(not(feature = "rayon"))]
+                let probs = model.predict_batch(data, num_features);
+                if let Some(verification) = verification {
+                    verification.compare(data, num_features, &probs);
+                }
+                if let Some(evaluation) = evaluation {
+                    for &prob in &probs {
+                        evaluation.record(usize::from(prob > args.threshold))?;
+                    }
+                }
+                let jsonl = matches!(args.output_format, PredictionOutput::Jsonl);
+                for (i, prob) in probs.into_iter().enumerate() {
+                    if jsonl {
+                        let label = u8::from(prob > args.threshold);
+                        out_file.write_all(jsonl_line(first_index + i, label, prob).as_bytes())?;
+                    } else if args.output_probabilities {
+                        out_file.write_all(format!("{}\n", prob).as_bytes())?;
+                    } else if let Some(bins) = args.cut_points() {
+                        out_file.write_all(format!("{}\n", bins.bin(prob)).as_bytes())?;
+                    } else {
+                        let prediction: u8 = if prob > args.threshold { 1 } else { 0 };
+                        out_file.write_all(format!("{}\n", prediction).as_bytes())?;
+                    }
+                }
+            }
+            Model::Svm(model) => {
+                let jsonl = matches!(args.output_format, PredictionOutput::Jsonl);
+                if args.output_probabilities || jsonl {
+                    #[cfg(feature = "rayon")]
+                    let scores = match POOL.get() {
+                        Some(pool) => pool
+                            .install(|| model.decision_function_batch_parallel(data, num_features)),
+                        None => model.decision_function_batch(data, num_features),
+                    };
+                    #[cfg(not(feature = "rayon"))]
+                    let scores = model.decision_function_batch(data, num_features);
+                    if let Some(evaluation) = evaluation {
+                        for &score in &scores {
+                            evaluation.record(usize::from(model.tie_break().label(score) > 0))?;
+                        }
+                    }
+                    for (i, score) in scores.into_iter().enumerate() {
+                        if jsonl {
+                            let label = model.tie_break().label(score);
+                            out_file
+                                .write_all(jsonl_line(first_index + i, label, score).as_bytes())?;
+                        } else {
+                            let value = model.calibrate(score).unwrap_or(score);
+                            out_file.write_all(format!("{}\n", value).as_bytes())?;
+                        }
+                    }
+                } else {
+                    #[cfg(feature = "rayon")]
+                    let predictions = match POOL.get() {
+                        Some(pool) => {
+                            pool.install(|| model.predict_batch_parallel(data, num_features))
+                        }
+                        None => model.predict_batch(data, num_features),
+                    };
+                    #[cfg(not(feature = "rayon"))]
+                    let predictions = model.predict_batch(data, num_features);
+                    if let Some(evaluation) = evaluation {
+                        for &prediction in &predictions {
+                            evaluation.record(usize::from(prediction > 0))?;
+                        }
+                    }
+                    for prediction in predictions {
+                        out_file.write_all(format!("{}\n", prediction).as_bytes())?;
+                    }
+                }
+            }
+            Model::NaiveBayes(model) => {
+                if args.output_probabilities {
+                    let mut evaluation = evaluation;
+                    for sample in data.chunks(num_features) {
+                        let proba = model.predict_proba(sample);
+                        if let Some(evaluation) = evaluation.as_deref_mut() {
+                            evaluation.record(best_class(&proba))?;
+                        }
+                        let proba: Vec<String> = proba.iter().map(|p| p.to_string()).collect();
+                        out_file.write_all(format!("{}\n", proba.join(",")).as_bytes())?;
+                    }
+                } else {
+                    #[cfg(feature = "rayon")]
+                    let classes = match POOL.get() {
+                        Some(pool) => {
+                            pool.install(|| model.predict_batch_parallel(data, num_features))
+                        }
+                        None => model.predict_batch(data, num_features),
+                    };
+                    #[cfg(not(feature = "rayon"))]
+                    let classes = model.predict_batch(data, num_features);
+                    if let Some(evaluation) = evaluation {
+                        for &class in &classes {
+                            evaluation.record(class)?;
+                        }
+                    }
+                    for class in classes {
+                        out_file.write_all(format!("{}\n", class).as_bytes())?;
+                    }
+                }
+            }
+            Model::Knn(model) => {
+                if args.output_probabilities {
+                    let mut evaluation = evaluation;
+                    for sample in data.chunks(num_features) {
+                        let proba = model.predict_proba(sample);
+                        if let Some(evaluation) = evaluation.as_deref_mut() {
+                            evaluation.record(best_class(&proba))?;
+                        }
+                        let proba: Vec<String> = proba.iter().map(|p| p.to_string()).collect();
+                        out_file.write_all(format!("{}\n", proba.join(",")).as_bytes())?;
+                    }
+                } else {
+                    #[cfg(feature = "rayon")]
+                    let classes = match POOL.get() {
+                        Some(pool) => {
+                            pool.install(|| model.predict_batch_parallel(data, num_features))
+                        }
+                        None => model.predict_batch(data, num_features),
+                    };
+                    #[cfg(not(feature = "rayon"))]
+                    let classes = model.predict_batch(data, num_features);
+                    if let Some(evaluation) = evaluation {
+                        for &class in &classes {
+                            evaluation.record(class)?;
+                        }
+                    }
+                    for class in classes {
+                        out_file.write_all(format!("{}\n", class).as_bytes())?;
+                    }
+                }
+            }
+            Model::Ensemble {
+                model,
+                svm,
+                tie_break,
+            } => {
+                let cutoff = ensemble_cutoff(model, *svm, args.threshold);
+                let scores = model.predict_batch(data, num_features);
+                if let Some(evaluation) = evaluation {
+                    for &score in &scores {
+                        let label = ensemble_label(score, cutoff, *svm, *tie_break);
+                        evaluation.record(usize::from(label > 0))?;
+                    }
+                }
+                for score in scores {
+                    if args.output_probabilities {
+                        out_file.write_all(format!("{}\n", score).as_bytes())?;
+                    } else {
+                        let prediction = ensemble_label(score, cutoff, *svm, *tie_break);
+                        out_file.write_all(format!("{}\n", prediction).as_bytes())?;
+                    }
+                }
             }
         }
+        Ok(())
     }
+}
 
-    let mut params_file = BufReader::new(File::open(args.parameters)?);
-    let mut params_bytes = Vec::new();
-    params_file.read_to_end(&mut params_bytes)?;
+impl Model {
+    /// Prediction and score of one --repl sample, e.g. "1 (probability 0.83)"
+    fn describe(&self, args: &Args, sample: &[f32]) -> Result<String, InferenceError> {
+        Ok(match self {
+            Model::Logistic(model) => {
+                let prob = model.try_predict(sample)?;
+                format!("{} (probability {})", u8::from(prob > args.threshold), prob)
+            }
+            Model::Svm(model) => {
+                let score = model.decision_function(sample)?;
+                let label = model.tie_break().label(score);
+                match model.calibrate(score) {
+                    Some(prob) => format!("{} (score {}, probability {})", label, score, prob),
+                    None => format!("{} (score {})", label, score),
+                }
+            }
+            Model::NaiveBayes(model) => describe_classes(&model.try_predict_proba(sample)?),
+            Model::Knn(model) => describe_classes(&model.try_predict_proba(sample)?),
+            Model::Ensemble {
+                model,
+                svm,
+                tie_break,
+            } => {
+                let score = model.try_predict(sample)?;
+                let cutoff = ensemble_cutoff(model, *svm, args.threshold);
+                let label = ensemble_label(score, cutoff, *svm, *tie_break);
+                format!("{} (score {})", label, score)
+            }
+        })
+    }
+}
 
-    // bytes -> f32 array
-    let params: Vec<f32> = params_bytes
-        .chunks(4)
-        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
-        .collect();
+fn describe_classes(proba: &[f32]) -> String {
+    let proba_list: Vec<String> = proba.iter().map(|p| p.to_string()).collect();
+    format!(
+        "{} (probabilities {})",
+        best_class(proba),
+        proba_list.join(",")
+    )
+}
 
-    // bias is last value in array
-    let bias = params[params.len() - 1];
-    let weights = params[..params.len() - 1].to_vec();
+/// Index of the most probable class, the first one on ties
+fn best_class(proba: &[f32]) -> usize {
+    (0..proba.len())
+        .reduce(|best, c| if proba[c] > proba[best] { c } else { best })
+        .unwrap_or(0)
+}
 
-    // Read data matrix
-    let mut data_file = BufReader::new(File::open(&args.data)?);
-    let mut data_bytes = Vec::new();
-    data_file.read_to_end(&mut data_bytes)?;
+/// Score above which an ensemble predicts the positive class. Votes are a
+/// fraction of members, so a strict majority is above 0.5.
+fn ensemble_cutoff(model: &Ensemble, svm: bool, threshold: f32) -> f32 {
+    match model.aggregation() {
+        Aggregation::MajorityVote => 0.5,
+        Aggregation::MeanScore if svm => 0.0,
+        Aggregation::MeanScore => threshold,
+    }
+}
 
-    // bytes -> f32 matrix
-    let data: Vec<f32> = data_bytes
-        .chunks(4)
-        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
-        .collect();
+/// Label for an ensemble score: 0/1 for logistic members, and -1/1 for SVM
+/// members with a score on the cutoff settled by --tie-break
+fn ensemble_label(score: f32, cutoff: f32, svm: bool, tie_break: TieBreak) -> i32 {
+    if svm {
+        tie_break.label(score - cutoff)
+    } else {
+        i32::from(score > cutoff)
+    }
+}
 
-    // Process based on model type
-    let num_features = weights.len();
-    let num_samples = data.len() / num_features;
-    println!("Number of samples: {}", num_samples);
-    println!("Feature dimension: {}", num_features);
+/// Reads comma separated samples from stdin until EOF and prints the prediction
+/// for each. A line that does not parse or has the wrong number of features gets
+/// an error message and the loop moves on to the next line.
+fn run_repl(model: &Model, args: &Args) -> Result<()> {
+    use std::io::{BufRead, IsTerminal};
 
-    match args.model {
-        ModelType::Logistic => {
-            let model = LogisticRegression::new(weights.clone(), bias, LogisticSequential);
-            for chunk in data.chunks(num_features) {
-                let prob = model.predict(chunk);
-                let prediction: u8 = if prob > 0.5 { 1 } else { 0 };
-                out_file.write_all(format!("{}\n", prediction).as_bytes())?;
+    let num_features = model.num_features();
+    let interactive = std::io::stdin().is_terminal();
+    if interactive {
+        eprintln!(
+            "Enter {} comma separated features per line, Ctrl-D to quit",
+            num_features
+        );
+    }
+    let prompt = || {
+        if interactive {
+            eprint!("> ");
+        }
+    };
+    prompt();
+    for line in std::io::stdin().lock().lines() {
+        let line = line.context("Failed to read stdin")?;
+        let line = line.trim();
+        if !line.is_empty() {
+            let sample: Result<Vec<f32>, _> =
+                line.split(',').map(|field| field.trim().parse()).collect();
+            match sample {
+                Ok(sample) => match model.describe(args, &sample) {
+                    Ok(prediction) => println!("{}", prediction),
+                    Err(error) => println!("error: {}", error),
+                },
+                Err(error) => println!("error: invalid value: {}", error),
             }
         }
-        ModelType::Svm => {
-            let model = SupportVectorMachine::new(weights.clone(), bias, SVMSequential);
+        prompt();
+    }
+    Ok(())
+}
 
-            for chunk in data.chunks(num_features) {
-                let prediction = model.predict(chunk);
-                out_file.write_all(format!("{}\n", prediction).as_bytes())?;
-            }
+/// One --output-format jsonl record, newline included
+fn jsonl_line(index: usize, label: impl Display, score: f32) -> String {
+    format!(
+        "{{\"index\": {}, \"label\": {}, \"score\": {}}}\n",
+        index,
+        label,
+        benchmarks::json_number(score as f64)
+    )
+}
+
+/// Ground-truth labels and the confusion matrix filled in as predictions are written
+struct Evaluation {
+    labels: Vec<usize>,
+    matrix: ConfusionMatrix,
+}
+
+impl Evaluation {
+    /// Counts the prediction for the next sample against its label
+    fn record(&mut self, predicted: usize) -> Result<()> {
+        let sample = self.matrix.total();
+        match self.labels.get(sample) {
+            Some(&actual) => self.matrix.record(actual, predicted),
+            None => bail!("Label file only holds {} labels", self.labels.len()),
         }
+        Ok(())
     }
+}
+
+/// Reads `--labels`, if given, mapping each label to the model's class index
+fn load_labels(args: &Args, model: &Model) -> Result<Option<Evaluation>> {
+    let Some(path) = &args.labels else {
+        return Ok(None);
+    };
+    let (values, _) = read_matrix(
+        path,
+        &args.format,
+        (&args.dtype).into(),
+        (&args.endian).into(),
+    )
+    .with_context(|| format!("Failed to read labels from {}", path.display()))?;
+    let labels = values
+        .iter()
+        .enumerate()
+        .map(|(i, &label)| match model.label_class(label) {
+            Some(class) => Ok(class),
+            None => bail!(
+                "Label {} at index {} of {} is not a valid {} class",
+                label,
+                i,
+                path.display(),
+                args.model
+            ),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Some(Evaluation {
+        labels,
+        matrix: ConfusionMatrix::new(model.num_classes()),
+    }))
+}
 
+/// Scalar copy of a logistic model that its predictions are checked against
+struct Verification {
+    reference: LogisticRegression<f32, Clip<Sequential>>,
+    max_difference: f32,
+}
+
+impl Verification {
+    /// Scores the block with the reference and tracks the largest difference.
+    /// A NaN on only one side counts as an infinite difference.
+    fn compare(&mut self, data: &[f32], num_features: usize, probs: &[f32]) {
+        let expected = self.reference.predict_batch(data, num_features);
+        for (&prob, &reference) in probs.iter().zip(&expected) {
+            let difference = match (prob.is_nan(), reference.is_nan()) {
+                (false, false) => (prob - reference).abs(),
+                (true, true) => 0.0,
+                _ => f32::INFINITY,
+            };
+            self.max_difference = self.max_difference.max(difference);
+        }
+    }
+}
+
+/// Builds the --verify reference, with the same clipping but the scalar dot product
+fn scalar_reference(model: &Model, args: &Args) -> Option<Verification> {
+    match (args.verify, model) {
+        (Some(_), Model::Logistic(model)) => {
+            let strategy = model.strategy();
+            let reference = model.with_strategy(Clip {
+                inner: Sequential,
+                input: strategy.input,
+                logit: strategy.logit,
+            });
+            Some(Verification {
+                reference,
+                max_difference: 0.0,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Prints the largest difference from the scalar reference, failing above the tolerance
+fn report_verification(args: &Args, verification: Verification) -> Result<()> {
+    let tolerance = args.verify.unwrap_or_default();
+    print_result(
+        args,
+        format!(
+            "Max absolute difference from scalar: {:e} (tolerance {:e})",
+            verification.max_difference, tolerance
+        ),
+    );
+    if verification.max_difference > tolerance {
+        bail!(
+            "Predictions differ from the scalar reference by {:e}, above the tolerance {:e}",
+            verification.max_difference,
+            tolerance
+        );
+    }
+    Ok(())
+}
+
+/// Checks every label was matched by a prediction, then prints the metrics
+fn report_evaluation(args: &Args, evaluation: Evaluation) -> Result<()> {
+    if evaluation.matrix.total() != evaluation.labels.len() {
+        bail!(
+            "Label file holds {} labels but the data has {} samples",
+            evaluation.labels.len(),
+            evaluation.matrix.total()
+        );
+    }
+    print_result(args, &evaluation.matrix);
     Ok(())
 }
 
+/// Prints the --summary line. Model names match the --model values.
+fn print_summary(args: &Args, num_samples: usize, num_features: usize) {
+    let model = match args.model {
+        ModelType::Logistic => "logistic",
+        ModelType::Svm => "svm",
+        ModelType::NaiveBayes => "naive-bayes",
+        ModelType::Knn => "knn",
+    };
+    print_result(
+        args,
+        format!(
+            "{{\"num_samples\":{},\"num_features\":{},\"model\":\"{}\"}}",
+            num_samples, num_features, model
+        ),
+    );
+}
+
+/// Prints the `count` largest weights by magnitude as "index<TAB>weight" lines,
+/// largest first. Equal magnitudes keep feature order.
+fn print_top_weights(args: &Args, weights: &[f32], count: usize) {
+    let mut ranked: Vec<(usize, f32)> = weights.iter().copied().enumerate().collect();
+    ranked.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+    for (index, weight) in ranked.into_iter().take(count) {
+        print_result(args, format!("{}\t{}", index, weight));
+    }
+}
+
+/// Prints a result on stdout, or on stderr when the predictions already go to stdout.
+/// Results are not diagnostics, so they bypass the log and --quiet.
+fn print_result(args: &Args, result: impl Display) {
+    if args.output == Path::new("-") {
+        eprintln!("{}", result);
+    } else {
+        println!("{}", result);
+    }
+}
+
+/// Turns a `[min, max]` flag value into a clamp range, rejecting inverted or NaN bounds
+fn clip_range(values: &Option<Vec<f32>>, flag: &str) -> Result<Option<(f32, f32)>> {
+    match values.as_deref() {
+        None => Ok(None),
+        Some(&[min, max]) if min <= max => Ok(Some((min, max))),
+        Some(values) => bail!("{} needs MIN <= MAX, got {:?}", flag, values),
+    }
+}
+
+/// Parses a decision threshold, rejecting anything outside [0, 1]
+fn parse_threshold(s: &str) -> std::result::Result<f32, String> {
+    let threshold: f32 = s.parse().map_err(|e| format!("{}", e))?;
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(format!("threshold must be in [0, 1], got {}", threshold));
+    }
+    Ok(threshold)
+}
+
+/// Parses --max-seconds as a positive, finite number of seconds
+fn parse_seconds(s: &str) -> std::result::Result<Duration, String> {
+    let seconds: f64 = s.parse().map_err(|e| format!("{}", e))?;
+    if seconds.is_nan() || seconds <= 0.0 {
+        return Err(format!("must be a positive number of seconds, got {}", s));
+    }
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("{}", e))
+}
+
+/// Parses --bins, checking every cut point like --threshold and that they increase
+fn parse_bins(s: &str) -> std::result::Result<CutPoints, String> {
+    if s.trim().is_empty() {
+        return Ok(CutPoints(Vec::new()));
+    }
+    let cuts = s
+        .split(',')
+        .map(|cut| parse_threshold(cut.trim()))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if !cuts.windows(2).all(|pair| pair[0] < pair[1]) {
+        return Err(format!(
+            "cut points must be strictly increasing, got {:?}",
+            cuts
+        ));
+    }
+    Ok(CutPoints(cuts))
+}
+
+/// Opens the prediction sink, treating "-" as stdout
+fn open_output(path: &Path) -> Result<Box<dyn Write>> {
+    if path == Path::new("-") {
+        Ok(Box::new(BufWriter::new(std::io::stdout().lock())))
+    } else {
+        Ok(Box::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+/// Fails when a parameter file header declares a different feature dimension
+/// than the model loaded from the file
+fn check_header(path: &Path, header: Option<ParamHeader>, num_features: usize) -> Result<()> {
+    if let Some(header) = header {
+        if header.num_features as usize != num_features {
+            bail!(
+                "{} declares {} features in its header but holds a model with {}",
+                path.display(),
+                header.num_features,
+                num_features
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Loads a further --parameters file, which must match the first model's
+/// `num_features`
+fn load_challenger(args: &Args, path: &Path, num_features: usize) -> Result<Model> {
+    let (model, header) = load_model(args, path)?;
+    check_header(path, header, model.num_features())?;
+    if model.num_features() != num_features {
+        bail!(
+            "{} describes {} features but {} describes {}",
+            path.display(),
+            model.num_features(),
+            args.parameters[0].display(),
+            num_features
+        );
+    }
+    Ok(model)
+}
+
+/// Reads the flat parameter buffer. Binary files may start with a header, whose
+/// model type must match --model.
+fn read_param_matrix(args: &Args, path: &Path) -> Result<(Vec<f32>, Option<ParamHeader>)> {
+    let read_context = || format!("Failed to read parameters from {}", path.display());
+    let endian = (&args.endian).into();
+    let (header, params) = match args.format {
+        DataFormat::Binary => {
+            io::read_f32_binary_with_header(path, endian).with_context(read_context)?
+        }
+        _ => (
+            None,
+            read_matrix(path, &args.format, io::DType::F32, endian)
+                .with_context(read_context)?
+                .0,
+        ),
+    };
+    check_declared_model(args, path, header)?;
+    check_params(validate::scan_values(&params), args.strict_checks())?;
+    Ok((params, header))
+}
+
+/// Fails when a parameter header declares a different model type than --model
+fn check_declared_model(args: &Args, path: &Path, header: Option<ParamHeader>) -> Result<()> {
+    if let Some(header) = header {
+        let declared = ModelType::from(header.model);
+        if declared != args.model {
+            bail!(
+                "{} declares a {} model but --model is {}",
+                path.display(),
+                declared,
+                args.model
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Reports NaN, infinite and subnormal parameters, failing on non-finite ones in strict mode
+fn check_params(report: validate::ParamReport, strict: bool) -> Result<()> {
+    if !report.non_finite.is_empty() {
+        let message = format!(
+            "Parameters contain NaN or infinite values at indices {}",
+            validate::describe_indices(&report.non_finite)
+        );
+        if strict {
+            bail!(message);
+        }
+        warn!("{}", message);
+    }
+    if !report.subnormal.is_empty() {
+        warn!(
+            "Subnormal parameters at indices {} may slow down SIMD inference",
+            validate::describe_indices(&report.subnormal)
+        );
+    }
+    Ok(())
+}
+
+/// Reports a data file without samples, which usually means a broken upstream
+/// step, failing in strict mode
+fn check_samples(path: &Path, num_samples: usize, strict: bool) -> Result<()> {
+    if num_samples == 0 {
+        let message = format!("Data file {} holds no samples", path.display());
+        if strict {
+            bail!(message);
+        }
+        warn!("{}", message);
+    }
+    Ok(())
+}
+
+/// Reads every data file and the labels for --validate-only, checking each data
+/// file for a whole, non-empty set of finite samples and the labels for one valid
+/// class per sample. Returns the total number of samples.
+fn validate_files(args: &Args, model: &Model, num_features: usize) -> Result<usize> {
+    let mut num_samples = 0;
+    for path in &args.data {
+        let data = load_data(
+            path,
+            &args.format,
+            (&args.dtype).into(),
+            (&args.endian).into(),
+            &args.data_layout,
+            num_features,
+        )?;
+        let report = validate::scan_values(data.as_slice());
+        if !report.non_finite.is_empty() {
+            bail!(
+                "Data file {} contains NaN or infinite values at indices {}",
+                path.display(),
+                validate::describe_indices(&report.non_finite)
+            );
+        }
+        check_samples(path, data.num_rows(), true)?;
+        info!("{}: {} samples", path.display(), data.num_rows());
+        num_samples += data.num_rows();
+    }
+    if let Some(evaluation) = load_labels(args, model)? {
+        if evaluation.labels.len() != num_samples {
+            bail!(
+                "Label file holds {} labels but the data files hold {} samples",
+                evaluation.labels.len(),
+                num_samples
+            );
+        }
+    }
+    Ok(num_samples)
+}
+
+/// Loads the data matrix in row-major order, checking that it holds a whole
+/// number of samples
+fn load_data(
+    path: &Path,
+    format: &DataFormat,
+    dtype: io::DType,
+    endian: io::Endian,
+    layout: &DataLayout,
+    num_features: usize,
+) -> Result<Matrix> {
+    if let (DataFormat::Binary, DataLayout::Row) = (format, layout) {
+        return inference::load_samples(path, dtype, endian, num_features)
+            .with_context(|| format!("Failed to read data from {}", path.display()));
+    }
+    let (data, data_columns) = read_matrix(path, format, dtype, endian)
+        .with_context(|| format!("Failed to read data from {}", path.display()))?;
+    if let Some(columns) = data_columns {
+        if columns != num_features {
+            bail!(
+                "Data file {} has {} columns but the model expects {} features",
+                path.display(),
+                columns,
+                num_features
+            );
+        }
+    }
+    let matrix = match layout {
+        DataLayout::Row => Matrix::new(data, num_features),
+        DataLayout::Col => Matrix::from_column_major(data, num_features),
+    };
+    matrix.with_context(|| format!("Invalid data file {}", path.display()))
+}
+
+/// Reads a flat f32 matrix, returning the column count when the format records one.
+/// `dtype` only applies to binary files.
+fn read_matrix(
+    path: &Path,
+    format: &DataFormat,
+    dtype: io::DType,
+    endian: io::Endian,
+) -> Result<(Vec<f32>, Option<usize>)> {
+    Ok(match format {
+        DataFormat::Binary => (io::read_binary(path, dtype, endian)?, None),
+        DataFormat::Csv => {
+            let (values, num_columns) = io::read_csv(path)?;
+            (values, Some(num_columns))
+        }
+        DataFormat::Npy => {
+            let array = npy::read_npy(path)?;
+            let num_columns = array.num_columns();
+            (array.data, num_columns)
+        }
+    })
+}
+
+/// Rayon pool for --threads, built once in `main` before any inference runs
+#[cfg(feature = "rayon")]
+static POOL: OnceLock<InferencePool> = OnceLock::new();
+
 #[cfg(test)]
 mod tests {
-    use super::Args;
+    use super::{
+        ensemble_label, jsonl_line, parse_bins, parse_seconds, parse_threshold, Args, TieBreak,
+    };
 
     use clap::CommandFactory;
 
@@ -206,4 +2089,59 @@ mod tests {
     fn verify_cli() {
         Args::command().debug_assert();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn threshold_outside_unit_interval_is_rejected() {
+        assert_eq!(parse_threshold("0.2"), Ok(0.2));
+        assert!(parse_threshold("1.5").is_err());
+        assert!(parse_threshold("-0.1").is_err());
+        assert!(parse_threshold("NaN").is_err());
+    }
+
+    #[test]
+    fn max_seconds_must_be_positive() {
+        assert_eq!(
+            parse_seconds("1.5"),
+            Ok(std::time::Duration::from_millis(1500))
+        );
+        assert!(parse_seconds("0").is_err());
+        assert!(parse_seconds("-2").is_err());
+        assert!(parse_seconds("NaN").is_err());
+        assert!(parse_seconds("inf").is_err());
+    }
+
+    #[test]
+    fn bins_are_sorted_probabilities() {
+        let bins = parse_bins("0.3, 0.7").unwrap();
+        let binned: Vec<usize> = [0.1, 0.3, 0.5, 0.7, 0.9]
+            .into_iter()
+            .map(|prob| bins.bin(prob))
+            .collect();
+        assert_eq!(binned, vec![0, 0, 1, 1, 2]);
+        assert!(parse_bins("").unwrap().0.is_empty());
+        assert!(parse_bins("0.7,0.3").is_err());
+        assert!(parse_bins("0.5,0.5").is_err());
+        assert!(parse_bins("0.5,1.5").is_err());
+    }
+
+    #[test]
+    fn jsonl_records_are_valid_json() {
+        assert_eq!(
+            jsonl_line(3, -1, -0.25),
+            "{\"index\": 3, \"label\": -1, \"score\": -0.25}\n"
+        );
+        assert_eq!(
+            jsonl_line(0, 0, f32::NAN),
+            "{\"index\": 0, \"label\": 0, \"score\": null}\n"
+        );
+    }
+
+    #[test]
+    fn svm_ensemble_ties_follow_tie_break() {
+        assert_eq!(ensemble_label(0.5, 0.5, true, TieBreak::Negative), -1);
+        assert_eq!(ensemble_label(0.5, 0.5, true, TieBreak::Positive), 1);
+        assert_eq!(ensemble_label(0.5, 0.5, true, TieBreak::Zero), 0);
+        assert_eq!(ensemble_label(0.75, 0.5, true, TieBreak::Zero), 1);
+        assert_eq!(ensemble_label(0.5, 0.5, false, TieBreak::Positive), 0);
+    }
+}
@@ -0,0 +1,250 @@
+// File: src/models/ensemble.rs
+//
+// This file implements an ensemble over any mix of `Predictor`s, aggregating
+// either by mean score or by majority vote. Members count equally unless mixing
+// weights are set, e.g. meta-weights fitted on a holdout set.
+//
+// Parameter file layout (flat little-endian f32, same encoding as the other models):
+//
+//     [num_models, num_features,
+//      model_0 parameters .. model_{n-1} parameters]
+//
+// Every member block holds `num_features` weights plus the bias, placed according
+// to the usual `BiasPosition`, so an ensemble file is the member files
+// concatenated behind a two value header.
+
+use crate::models::error::{header_count, InferenceError};
+use crate::models::predictor::Predictor;
+use crate::utils::io::{split_bias, BiasPosition};
+
+// Number of header values preceding the member blocks
+const HEADER_LEN: usize = 2;
+
+// Allowed distance of the mixing weights' sum from 1
+const MIXING_TOLERANCE: f32 = 1e-4;
+
+/// How member predictions are combined
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Mean of the member scores. Only meaningful when the members share a
+    /// score scale, e.g. all logistic probabilities.
+    #[default]
+    MeanScore,
+    /// Fraction of members labelling the sample positive
+    MajorityVote,
+}
+
+pub struct Ensemble {
+    members: Vec<Box<dyn Predictor>>,
+    aggregation: Aggregation,
+    // One weight per member summing to 1, or None for the plain mean
+    mixing_weights: Option<Vec<f32>>,
+}
+
+impl Ensemble {
+    pub fn new(
+        members: Vec<Box<dyn Predictor>>,
+        aggregation: Aggregation,
+    ) -> Result<Self, InferenceError> {
+        let Some(first) = members.first() else {
+            return Err(InferenceError::InvalidParameters(
+                "an ensemble needs at least one model".to_string(),
+            ));
+        };
+        let num_features = first.num_features();
+        if let Some(member) = members.iter().find(|m| m.num_features() != num_features) {
+            return Err(InferenceError::DimensionMismatch {
+                expected: num_features,
+                got: member.num_features(),
+            });
+        }
+        Ok(Self {
+            members,
+            aggregation,
+            mixing_weights: None,
+        })
+    }
+
+    /// Weights member `i`'s score (or vote) by `weights[i]` instead of 1/n. The
+    /// weights must be non-negative, one per member and sum to 1.
+    pub fn with_mixing_weights(mut self, weights: Vec<f32>) -> Result<Self, InferenceError> {
+        if weights.len() != self.members.len() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: self.members.len(),
+                got: weights.len(),
+            });
+        }
+        // also rejects NaN, which fails every comparison
+        if !weights.iter().all(|&w| w >= 0.0) {
+            return Err(InferenceError::InvalidParameters(
+                "mixing weights must be non-negative".to_string(),
+            ));
+        }
+        let sum: f32 = weights.iter().sum();
+        if (sum - 1.0).abs() > MIXING_TOLERANCE {
+            return Err(InferenceError::InvalidParameters(format!(
+                "mixing weights must sum to 1, found {}",
+                sum
+            )));
+        }
+        self.mixing_weights = Some(weights);
+        Ok(self)
+    }
+
+    pub fn mixing_weights(&self) -> Option<&[f32]> {
+        self.mixing_weights.as_deref()
+    }
+
+    /// Builds an ensemble from the layout documented above, turning each member's
+    /// weights and bias into a model with `build`
+    pub fn from_params<P: Predictor + 'static>(
+        params: &[f32],
+        bias_position: BiasPosition,
+        aggregation: Aggregation,
+        build: impl Fn(Vec<f32>, f32) -> P,
+    ) -> Result<Self, InferenceError> {
+        if params.len() < HEADER_LEN {
+            return Err(InferenceError::InvalidParameters(format!(
+                "expected a {} value header, found {} values",
+                HEADER_LEN,
+                params.len()
+            )));
+        }
+        let num_models = header_count(params[0], "number of models")?;
+        let num_features = header_count(params[1], "number of features")?;
+
+        let block = match bias_position {
+            BiasPosition::None => num_features,
+            BiasPosition::Last | BiasPosition::First => num_features + 1,
+        };
+        let expected = HEADER_LEN + num_models * block;
+        if num_models == 0 || num_features == 0 || params.len() != expected {
+            return Err(InferenceError::InvalidParameters(format!(
+                "{} models of dimension {} need {} values, found {}",
+                num_models,
+                num_features,
+                expected,
+                params.len()
+            )));
+        }
+
+        let members = params[HEADER_LEN..]
+            .chunks(block)
+            .map(|member| {
+                let (weights, bias) = split_bias(member.to_vec(), bias_position)
+                    .map_err(|e| InferenceError::InvalidParameters(e.to_string()))?;
+                Ok(Box::new(build(weights, bias)) as Box<dyn Predictor>)
+            })
+            .collect::<Result<Vec<_>, InferenceError>>()?;
+        Self::new(members, aggregation)
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.members[0].num_features()
+    }
+
+    pub fn aggregation(&self) -> Aggregation {
+        self.aggregation
+    }
+
+    /// Mean member score, or the fraction of positive votes for `MajorityVote`,
+    /// weighted by the mixing weights when they are set
+    pub fn predict(&self, input: &[f32]) -> f32 {
+        self.try_predict(input).unwrap()
+    }
+
+    pub fn try_predict(&self, input: &[f32]) -> Result<f32, InferenceError> {
+        let mut total = 0.0;
+        for (i, member) in self.members.iter().enumerate() {
+            let value = match self.aggregation {
+                Aggregation::MeanScore => member.try_predict_score(input)?,
+                Aggregation::MajorityVote => {
+                    f32::from(u8::from(member.try_predict_label(input)? > 0))
+                }
+            };
+            total += match &self.mixing_weights {
+                Some(weights) => weights[i] * value,
+                None => value,
+            };
+        }
+        Ok(match self.mixing_weights {
+            Some(_) => total,
+            None => total / self.members.len() as f32,
+        })
+    }
+
+    pub fn predict_batch(&self, data: &[f32], num_features: usize) -> Vec<f32> {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.chunks(num_features)
+            .map(|chunk| self.predict(chunk))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::logistic::base::{LogisticRegression, Sequential};
+
+    #[test]
+    fn mean_and_vote_over_concatenated_members() {
+        // three 1-D models with the bias last: x - 1, x + 1 and -x
+        let params = [3.0, 1.0, 1.0, -1.0, 1.0, 1.0, -1.0, 0.0];
+        let build = |weights, bias| LogisticRegression::new(weights, bias, Sequential);
+
+        let mean =
+            Ensemble::from_params(&params, BiasPosition::Last, Aggregation::MeanScore, build)
+                .unwrap();
+        assert_eq!(mean.len(), 3);
+        let members: Vec<f32> = [(1.0, -1.0), (1.0, 1.0), (-1.0, 0.0)]
+            .iter()
+            .map(|&(w, b)| LogisticRegression::new(vec![w], b, Sequential).predict(&[2.0]))
+            .collect();
+        let expected = members.iter().sum::<f32>() / 3.0;
+        assert!((mean.predict(&[2.0]) - expected).abs() < 1e-6);
+
+        // at x = 2 the first two models vote positive and the third negative
+        let vote = Ensemble::from_params(
+            &params,
+            BiasPosition::Last,
+            Aggregation::MajorityVote,
+            build,
+        )
+        .unwrap();
+        assert!((vote.predict(&[2.0]) - 2.0 / 3.0).abs() < 1e-6);
+
+        let weighted =
+            Ensemble::from_params(&params, BiasPosition::Last, Aggregation::MeanScore, build)
+                .unwrap()
+                .with_mixing_weights(vec![0.7, 0.3, 0.0])
+                .unwrap();
+        let expected = 0.7 * members[0] + 0.3 * members[1];
+        assert!((weighted.predict(&[2.0]) - expected).abs() < 1e-6);
+        for invalid in [vec![0.5, 0.5], vec![0.5, 0.6, -0.1], vec![0.5, 0.4, 0.0]] {
+            let ensemble =
+                Ensemble::from_params(&params, BiasPosition::Last, Aggregation::MeanScore, build)
+                    .unwrap();
+            assert!(ensemble.with_mixing_weights(invalid).is_err());
+        }
+
+        assert!(Ensemble::from_params(
+            &params[..7],
+            BiasPosition::Last,
+            Aggregation::MeanScore,
+            build
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,62 @@
+// File: src/models/error.rs
+//
+// Error types shared by all models.
+
+use std::fmt::{Display, Formatter};
+
+// Errors that can occur while running inference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InferenceError {
+    /// Input length does not match the number of model weights
+    DimensionMismatch { expected: usize, got: usize },
+    /// Parameter buffer does not follow the expected layout
+    InvalidParameters(String),
+    /// Sparse input refers to a feature the model does not have
+    FeatureIndexOutOfRange { index: usize, num_features: usize },
+    /// Categorical input is not a whole index below its cardinality
+    CategoryOutOfRange { position: usize, cardinality: usize },
+}
+
+impl Display for InferenceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InferenceError::DimensionMismatch { expected, got } => write!(
+                f,
+                "Input dimension mismatch: expected {} features, got {}",
+                expected, got
+            ),
+            InferenceError::InvalidParameters(reason) => {
+                write!(f, "Invalid model parameters: {}", reason)
+            }
+            InferenceError::FeatureIndexOutOfRange {
+                index,
+                num_features,
+            } => write!(
+                f,
+                "Feature index {} out of range for {} features",
+                index, num_features
+            ),
+            InferenceError::CategoryOutOfRange {
+                position,
+                cardinality,
+            } => write!(
+                f,
+                "Input {} is not a category index below {}",
+                position, cardinality
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InferenceError {}
+
+// Parses a count stored as f32 in a parameter file header
+pub(crate) fn header_count(value: f32, name: &str) -> Result<usize, InferenceError> {
+    if value < 0.0 || value.fract() != 0.0 || !value.is_finite() {
+        return Err(InferenceError::InvalidParameters(format!(
+            "{} must be a non-negative integer, found {}",
+            name, value
+        )));
+    }
+    Ok(value as usize)
+}
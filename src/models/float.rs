@@ -0,0 +1,58 @@
+// File: src/models/float.rs
+//
+// Minimal floating point abstraction so models can run in either single or
+// double precision. SIMD strategies only implement the f32 instantiation.
+
+use std::fmt::Debug;
+use std::iter::Sum;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// Floating point types usable as model weights and inputs
+pub trait Float:
+    Copy
+    + Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Sum
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn exp(self) -> Self;
+
+    fn ln(self) -> Self;
+}
+
+impl Float for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    #[inline]
+    fn exp(self) -> Self {
+        f32::exp(self)
+    }
+
+    #[inline]
+    fn ln(self) -> Self {
+        f32::ln(self)
+    }
+}
+
+impl Float for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    #[inline]
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+
+    #[inline]
+    fn ln(self) -> Self {
+        f64::ln(self)
+    }
+}
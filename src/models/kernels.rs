@@ -0,0 +1,893 @@
+// File: src/models/kernels.rs
+//
+// Dot product kernels shared by every linear model. Each model's strategies
+// wrap these and only differ in how the resulting score is activated, so any
+// fix to the vectorized loops applies to all models at once.
+//
+// SIMD Register Types:
+// - SSE: 128-bit registers that can process 4 x 32-bit floats in parallel
+// - AVX: 256-bit registers that can process 8 x 32-bit floats in parallel
+// - AVX-512: 512-bit registers that can process 16 x 32-bit floats in parallel
+// - NEON: 128-bit registers that can process 4 x 32-bit floats in parallel
+// - WASM SIMD128: 128-bit registers that can process 4 x 32-bit floats in parallel
+//
+// Inputs shorter than one register skip the SIMD setup and run the scalar loop.
+
+use crate::models::float::Float;
+#[cfg(target_arch = "aarch64")]
+use std::arch::aarch64::*;
+#[cfg(target_arch = "aarch64")]
+use std::arch::is_aarch64_feature_detected;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+use std::arch::wasm32::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+#[cfg(feature = "portable_simd")]
+use std::simd::{f32x8, num::SimdFloat};
+
+/// Portable dot product used as the fallback path and as the reference every
+/// SIMD kernel and strategy is tested against. Every `Sequential` strategy
+/// calls it, so downstream fuzzing can import it as the known-good inner product.
+#[inline]
+pub fn dot_scalar<F: Float>(weights: &[F], input: &[F]) -> F {
+    weights.iter().zip(input.iter()).map(|(&w, &x)| w * x).sum()
+}
+
+// Dot product with a sparse input given as (index, value) pairs. Only the
+// listed features are touched, so the cost follows the number of nonzeros.
+// Indices must be in bounds; repeated indices add up.
+#[inline]
+pub fn sparse_dot_scalar<F: Float>(weights: &[F], input: &[(u32, F)]) -> F {
+    input
+        .iter()
+        .map(|&(index, x)| weights[index as usize] * x)
+        .sum()
+}
+
+// Portable squared euclidean distance used by distance-based models
+#[inline]
+pub fn squared_distance_scalar<F: Float>(a: &[F], b: &[F]) -> F {
+    a.iter()
+        .zip(b.iter())
+        .map(|(&x, &y)| {
+            let d = x - y;
+            d * d
+        })
+        .sum()
+}
+
+// Portable squared euclidean distance with a weight per dimension
+#[inline]
+pub fn weighted_squared_distance_scalar<F: Float>(a: &[F], b: &[F], weights: &[F]) -> F {
+    a.iter()
+        .zip(b.iter())
+        .zip(weights.iter())
+        .map(|((&x, &y), &w)| {
+            let d = x - y;
+            d * d * w
+        })
+        .sum()
+}
+
+// SSE dot product using 128-bit registers
+#[cfg(target_arch = "x86_64")]
+pub fn dot_sse(weights: &[f32], input: &[f32]) -> f32 {
+    if weights.len() < 4 {
+        return dot_scalar(weights, input);
+    }
+
+    unsafe {
+        let feature_dim = weights.len();
+        // The vector loads only bounds-check their first lane, so bound the input here
+        let input = &input[..feature_dim];
+        let mut sum_vec = _mm_setzero_ps();
+        let mut i = 0;
+
+        // Check 16-byte alignment for optimal SSE performance
+        let aligned = both_aligned(weights, input, 16);
+
+        // Process 4 elements at a time using SSE
+        if aligned {
+            while i + 4 <= feature_dim {
+                debug_assert!(both_aligned(&weights[i..], &input[i..], 16));
+                let x_vec = _mm_load_ps(&input[i]);
+                let w_vec = _mm_load_ps(&weights[i]);
+                sum_vec = _mm_add_ps(sum_vec, _mm_mul_ps(x_vec, w_vec));
+                i += 4;
+            }
+        } else {
+            while i + 4 <= feature_dim {
+                let x_vec = _mm_loadu_ps(&input[i]);
+                let w_vec = _mm_loadu_ps(&weights[i]);
+                sum_vec = _mm_add_ps(sum_vec, _mm_mul_ps(x_vec, w_vec));
+                i += 4;
+            }
+        }
+
+        // Handle remaining elements sequentially
+        let mut sum_scalar = 0.0;
+        while i < feature_dim {
+            sum_scalar += input[i] * weights[i];
+            i += 1;
+        }
+
+        // Combine SSE vector sum with scalar sum
+        hsum128_ps(sum_vec) + sum_scalar
+    }
+}
+
+// SSE4.1 dot product using `_mm_dp_ps`, which multiplies and sums 4 lanes in one
+// instruction. Each dpps result is independent, so only the cheap lane-0 adds
+// form a dependency chain and no horizontal sum is needed at the end. Falls back
+// to `dot_sse` when the CPU lacks SSE4.1.
+#[cfg(target_arch = "x86_64")]
+pub fn dot_sse41(weights: &[f32], input: &[f32]) -> f32 {
+    if weights.len() < 4 {
+        return dot_scalar(weights, input);
+    }
+    if !is_x86_feature_detected!("sse4.1") {
+        return dot_sse(weights, input);
+    }
+
+    unsafe { dot_sse41_unchecked(weights, input) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.1")]
+unsafe fn dot_sse41_unchecked(weights: &[f32], input: &[f32]) -> f32 {
+    let feature_dim = weights.len();
+    let input = &input[..feature_dim];
+    let mut sum_vec = _mm_setzero_ps();
+    let mut i = 0;
+
+    while i + 4 <= feature_dim {
+        let x_vec = _mm_loadu_ps(&input[i]);
+        let w_vec = _mm_loadu_ps(&weights[i]);
+        // 0xF1: multiply all 4 lanes, write the sum to lane 0 only
+        sum_vec = _mm_add_ss(sum_vec, _mm_dp_ps::<0xF1>(x_vec, w_vec));
+        i += 4;
+    }
+
+    let mut sum_scalar = 0.0;
+    while i < feature_dim {
+        sum_scalar += input[i] * weights[i];
+        i += 1;
+    }
+
+    _mm_cvtss_f32(sum_vec) + sum_scalar
+}
+
+/// Horizontal sum of the 4 lanes of an SSE register, kept in registers instead
+/// of spilling to the stack. Uses movehl/shuffle rather than `_mm_hadd_ps` since
+/// hadd needs SSE3, which is not in the x86_64 baseline, and decodes to the same
+/// shuffles anyway.
+///
+/// # Safety
+/// Only uses SSE, which every x86_64 CPU supports.
+#[cfg(target_arch = "x86_64")]
+#[inline]
+pub unsafe fn hsum128_ps(v: __m128) -> f32 {
+    let high = _mm_movehl_ps(v, v); // [2, 3, 2, 3]
+    let pairs = _mm_add_ps(v, high); // [0+2, 1+3, ..]
+    let odd = _mm_shuffle_ps(pairs, pairs, 0b01); // [1+3, ..]
+    _mm_cvtss_f32(_mm_add_ss(pairs, odd))
+}
+
+/// Horizontal sum of the 8 lanes of an AVX register: folds the upper 128-bit
+/// half onto the lower one, then reduces that with `hsum128_ps`.
+///
+/// # Safety
+/// The CPU must support AVX.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+#[inline]
+pub unsafe fn hsum256_ps(v: __m256) -> f32 {
+    let high = _mm256_extractf128_ps(v, 1);
+    let low = _mm256_castps256_ps128(v);
+    hsum128_ps(_mm_add_ps(low, high))
+}
+
+// Whether both slices start on an `align`-byte boundary. The aligned loops only
+// ever step by whole registers of exactly `align` bytes (and the AVX loop by
+// multiples of that), so an aligned start keeps every later load aligned too.
+// Anything else, including any offset that is not a multiple of the register
+// width, takes the unaligned loads.
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+fn both_aligned(a: &[f32], b: &[f32], align: usize) -> bool {
+    (a.as_ptr() as usize).is_multiple_of(align) && (b.as_ptr() as usize).is_multiple_of(align)
+}
+
+// AVX dot product using 256-bit registers.
+//
+// The main loop keeps four independent accumulators so consecutive adds do not
+// wait on each other, which hides the add latency for long feature vectors.
+//
+// CPUs with FMA (detected separately from AVX) take the fused multiply-add
+// path, which is both faster and rounds once per element instead of twice.
+// CPUs without AVX fall back to SSE.
+#[cfg(target_arch = "x86_64")]
+pub fn dot_avx(weights: &[f32], input: &[f32]) -> f32 {
+    if weights.len() < 8 {
+        return dot_scalar(weights, input);
+    }
+    if !is_x86_feature_detected!("avx") {
+        return dot_sse(weights, input);
+    }
+    if is_x86_feature_detected!("fma") {
+        return unsafe { dot_avx_fma_unchecked(weights, input) };
+    }
+
+    unsafe { dot_avx_unchecked(weights, input) }
+}
+
+// AVX2 dot product, always using the fused multiply-add loop. AVX2 and FMA are
+// separate CPUID bits even though every AVX2 CPU in practice has both, so this
+// falls back to the AVX kernel when either is missing. Kept apart from `dot_avx`
+// so AVX2-only paths (int8 dot products for quantized weights) have a home.
+#[cfg(target_arch = "x86_64")]
+pub fn dot_avx2(weights: &[f32], input: &[f32]) -> f32 {
+    if weights.len() < 8 {
+        return dot_scalar(weights, input);
+    }
+    if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+        return unsafe { dot_avx_fma_unchecked(weights, input) };
+    }
+
+    dot_avx(weights, input)
+}
+
+// The caller must have checked that the CPU has AVX. `Auto` calls this directly
+// after checking once at construction.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+pub(crate) unsafe fn dot_avx_unchecked(weights: &[f32], input: &[f32]) -> f32 {
+    let feature_dim = weights.len();
+    // The vector loads only bounds-check their first lane, so bound the input here
+    let input = &input[..feature_dim];
+    let mut acc = [_mm256_setzero_ps(); 4];
+    let mut i = 0;
+
+    // Check 32-byte alignment for optimal AVX performance
+    let aligned = both_aligned(weights, input, 32);
+    let load = |ptr: &f32| {
+        if aligned {
+            debug_assert_eq!(ptr as *const f32 as usize % 32, 0);
+            _mm256_load_ps(ptr)
+        } else {
+            _mm256_loadu_ps(ptr)
+        }
+    };
+
+    // Process 32 elements at a time across 4 accumulators
+    while i + 32 <= feature_dim {
+        for (k, acc) in acc.iter_mut().enumerate() {
+            let x_vec = load(&input[i + 8 * k]);
+            let w_vec = load(&weights[i + 8 * k]);
+            *acc = _mm256_add_ps(*acc, _mm256_mul_ps(x_vec, w_vec));
+        }
+        i += 32;
+    }
+
+    // Process remaining full vectors 8 elements at a time
+    while i + 8 <= feature_dim {
+        let x_vec = load(&input[i]);
+        let w_vec = load(&weights[i]);
+        acc[0] = _mm256_add_ps(acc[0], _mm256_mul_ps(x_vec, w_vec));
+        i += 8;
+    }
+
+    // Handle remaining elements sequentially
+    let mut sum_scalar = 0.0;
+    while i < feature_dim {
+        sum_scalar += input[i] * weights[i];
+        i += 1;
+    }
+
+    // Combine the accumulators, then the AVX vector sum with scalar sum
+    let sum_vec = _mm256_add_ps(_mm256_add_ps(acc[0], acc[1]), _mm256_add_ps(acc[2], acc[3]));
+    hsum256_ps(sum_vec) + sum_scalar
+}
+
+// The caller must have checked that the CPU has AVX and FMA
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx,fma")]
+pub(crate) unsafe fn dot_avx_fma_unchecked(weights: &[f32], input: &[f32]) -> f32 {
+    let feature_dim = weights.len();
+    // The vector loads only bounds-check their first lane, so bound the input here
+    let input = &input[..feature_dim];
+    let mut acc = [_mm256_setzero_ps(); 4];
+    let mut i = 0;
+
+    // Check 32-byte alignment for optimal AVX performance
+    let aligned = both_aligned(weights, input, 32);
+    let load = |ptr: &f32| {
+        if aligned {
+            debug_assert_eq!(ptr as *const f32 as usize % 32, 0);
+            _mm256_load_ps(ptr)
+        } else {
+            _mm256_loadu_ps(ptr)
+        }
+    };
+
+    // Process 32 elements at a time across 4 accumulators
+    while i + 32 <= feature_dim {
+        for (k, acc) in acc.iter_mut().enumerate() {
+            let x_vec = load(&input[i + 8 * k]);
+            let w_vec = load(&weights[i + 8 * k]);
+            *acc = _mm256_fmadd_ps(x_vec, w_vec, *acc);
+        }
+        i += 32;
+    }
+
+    // Process remaining full vectors 8 elements at a time
+    while i + 8 <= feature_dim {
+        let x_vec = load(&input[i]);
+        let w_vec = load(&weights[i]);
+        acc[0] = _mm256_fmadd_ps(x_vec, w_vec, acc[0]);
+        i += 8;
+    }
+
+    // Handle remaining elements sequentially
+    let mut sum_scalar = 0.0;
+    while i < feature_dim {
+        sum_scalar += input[i] * weights[i];
+        i += 1;
+    }
+
+    // Combine the accumulators, then the AVX vector sum with scalar sum
+    let sum_vec = _mm256_add_ps(_mm256_add_ps(acc[0], acc[1]), _mm256_add_ps(acc[2], acc[3]));
+    hsum256_ps(sum_vec) + sum_scalar
+}
+
+// AVX-512 dot product using 512-bit registers, falling back to scalar when
+// the CPU does not support AVX-512F. Any length runs fully vectorized, since
+// the last partial chunk is loaded under a mask.
+#[cfg(target_arch = "x86_64")]
+pub fn dot_avx512(weights: &[f32], input: &[f32]) -> f32 {
+    if !is_x86_feature_detected!("avx512f") {
+        return dot_scalar(weights, input);
+    }
+
+    unsafe { dot_avx512_unchecked(weights, input) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+unsafe fn dot_avx512_unchecked(weights: &[f32], input: &[f32]) -> f32 {
+    let feature_dim = weights.len();
+    // The masked tail load reads through raw pointers, so bound the input here
+    let input = &input[..feature_dim];
+    let mut sum_vec = _mm512_setzero_ps();
+    let mut i = 0;
+
+    // Check 64-byte alignment for optimal AVX-512 performance
+    let aligned = both_aligned(weights, input, 64);
+
+    // Process 16 elements at a time using AVX-512
+    if aligned {
+        while i + 16 <= feature_dim {
+            debug_assert!(both_aligned(&weights[i..], &input[i..], 64));
+            let x_vec = _mm512_load_ps(&input[i]);
+            let w_vec = _mm512_load_ps(&weights[i]);
+            sum_vec = _mm512_fmadd_ps(x_vec, w_vec, sum_vec);
+            i += 16;
+        }
+    } else {
+        while i + 16 <= feature_dim {
+            let x_vec = _mm512_loadu_ps(&input[i]);
+            let w_vec = _mm512_loadu_ps(&weights[i]);
+            sum_vec = _mm512_fmadd_ps(x_vec, w_vec, sum_vec);
+            i += 16;
+        }
+    }
+
+    // Load the last partial chunk under a mask instead of looping over it.
+    // Masked-off lanes read as zero and never touch memory.
+    let remainder = feature_dim - i;
+    if remainder > 0 {
+        let mask = ((1u32 << remainder) - 1) as __mmask16;
+        let x_vec = _mm512_maskz_loadu_ps(mask, input.as_ptr().add(i));
+        let w_vec = _mm512_maskz_loadu_ps(mask, weights.as_ptr().add(i));
+        sum_vec = _mm512_fmadd_ps(x_vec, w_vec, sum_vec);
+    }
+
+    _mm512_reduce_add_ps(sum_vec)
+}
+
+// NEON dot product using 128-bit registers, falling back to scalar when
+// the CPU does not support NEON
+#[cfg(target_arch = "aarch64")]
+pub fn dot_neon(weights: &[f32], input: &[f32]) -> f32 {
+    if weights.len() < 4 || !is_aarch64_feature_detected!("neon") {
+        return dot_scalar(weights, input);
+    }
+
+    unsafe {
+        let feature_dim = weights.len();
+        let input = &input[..feature_dim];
+        let mut sum_vec = vdupq_n_f32(0.0);
+        let mut i = 0;
+
+        // Process 4 elements at a time using NEON
+        while i + 4 <= feature_dim {
+            let x_vec = vld1q_f32(&input[i]);
+            let w_vec = vld1q_f32(&weights[i]);
+            sum_vec = vfmaq_f32(sum_vec, x_vec, w_vec);
+            i += 4;
+        }
+
+        // Handle remaining elements sequentially
+        let mut sum_scalar = 0.0;
+        while i < feature_dim {
+            sum_scalar += input[i] * weights[i];
+            i += 1;
+        }
+
+        // Combine NEON vector sum with scalar sum
+        vaddvq_f32(sum_vec) + sum_scalar
+    }
+}
+
+// WebAssembly SIMD128 dot product using 128-bit registers. WASM has no runtime
+// feature detection, so this only exists when the module is built with
+// `-C target-feature=+simd128` and every engine running it must support SIMD.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+pub fn dot_simd128(weights: &[f32], input: &[f32]) -> f32 {
+    if weights.len() < 4 {
+        return dot_scalar(weights, input);
+    }
+
+    let feature_dim = weights.len();
+    let mut sum_vec = f32x4_splat(0.0);
+    let mut i = 0;
+
+    // Process 4 elements at a time using SIMD128. WASM loads have no alignment
+    // requirement, so there is no aligned branch.
+    while i + 4 <= feature_dim {
+        // SAFETY: slicing bounds-checks both 4-element ranges, so each 16-byte
+        // load stays in bounds
+        let (x_vec, w_vec) = unsafe {
+            (
+                v128_load(input[i..i + 4].as_ptr() as *const v128),
+                v128_load(weights[i..i + 4].as_ptr() as *const v128),
+            )
+        };
+        sum_vec = f32x4_add(sum_vec, f32x4_mul(x_vec, w_vec));
+        i += 4;
+    }
+
+    // Handle remaining elements sequentially
+    let sum_scalar = dot_scalar(&weights[i..], &input[i..]);
+
+    // Combine SIMD128 vector sum with scalar sum
+    f32x4_extract_lane::<0>(sum_vec)
+        + f32x4_extract_lane::<1>(sum_vec)
+        + f32x4_extract_lane::<2>(sum_vec)
+        + f32x4_extract_lane::<3>(sum_vec)
+        + sum_scalar
+}
+
+// Portable dot product over 8-lane std::simd vectors. Lowers to whatever the
+// target offers (SSE/AVX, NEON, wasm simd128) or plain scalar code otherwise.
+#[cfg(feature = "portable_simd")]
+pub fn dot_portable(weights: &[f32], input: &[f32]) -> f32 {
+    let mut sum_vec = f32x8::splat(0.0);
+    let weight_chunks = weights.chunks_exact(8);
+    let input_chunks = input.chunks_exact(8);
+    let tail = dot_scalar(weight_chunks.remainder(), input_chunks.remainder());
+
+    // Process 8 elements at a time
+    for (w, x) in weight_chunks.zip(input_chunks) {
+        sum_vec += f32x8::from_slice(w) * f32x8::from_slice(x);
+    }
+
+    sum_vec.reduce_sum() + tail
+}
+
+// SSE squared euclidean distance using 128-bit registers
+#[cfg(target_arch = "x86_64")]
+pub fn squared_distance_sse(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() < 4 {
+        return squared_distance_scalar(a, b);
+    }
+
+    unsafe {
+        let dim = a.len();
+        // The vector loads only bounds-check their first lane, so bound `b` here
+        let b = &b[..dim];
+        let mut sum_vec = _mm_setzero_ps();
+        let mut i = 0;
+
+        // Process 4 elements at a time using SSE
+        while i + 4 <= dim {
+            let d = _mm_sub_ps(_mm_loadu_ps(&a[i]), _mm_loadu_ps(&b[i]));
+            sum_vec = _mm_add_ps(sum_vec, _mm_mul_ps(d, d));
+            i += 4;
+        }
+
+        // Handle remaining elements sequentially
+        hsum128_ps(sum_vec) + squared_distance_scalar(&a[i..], &b[i..])
+    }
+}
+
+// AVX squared euclidean distance using 256-bit registers. Like `dot_avx` it
+// keeps four accumulators and squares the difference with a fused multiply-add
+// when the CPU has FMA, falling back to SSE without AVX.
+#[cfg(target_arch = "x86_64")]
+pub fn squared_distance_avx(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() < 8 {
+        return squared_distance_scalar(a, b);
+    }
+    if !is_x86_feature_detected!("avx") {
+        return squared_distance_sse(a, b);
+    }
+    if is_x86_feature_detected!("fma") {
+        return unsafe { squared_distance_avx_fma_unchecked(a, b) };
+    }
+
+    unsafe { squared_distance_avx_unchecked(a, b) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn squared_distance_avx_unchecked(a: &[f32], b: &[f32]) -> f32 {
+    let dim = a.len();
+    // The vector loads only bounds-check their first lane, so bound `b` here
+    let b = &b[..dim];
+    let mut acc = [_mm256_setzero_ps(); 4];
+    let mut i = 0;
+
+    // Process 32 elements at a time across 4 accumulators
+    while i + 32 <= dim {
+        for (k, acc) in acc.iter_mut().enumerate() {
+            let d = _mm256_sub_ps(
+                _mm256_loadu_ps(&a[i + 8 * k]),
+                _mm256_loadu_ps(&b[i + 8 * k]),
+            );
+            *acc = _mm256_add_ps(*acc, _mm256_mul_ps(d, d));
+        }
+        i += 32;
+    }
+
+    // Process remaining full vectors 8 elements at a time
+    while i + 8 <= dim {
+        let d = _mm256_sub_ps(_mm256_loadu_ps(&a[i]), _mm256_loadu_ps(&b[i]));
+        acc[0] = _mm256_add_ps(acc[0], _mm256_mul_ps(d, d));
+        i += 8;
+    }
+
+    let sum_vec = _mm256_add_ps(_mm256_add_ps(acc[0], acc[1]), _mm256_add_ps(acc[2], acc[3]));
+    hsum256_ps(sum_vec) + squared_distance_scalar(&a[i..], &b[i..])
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx,fma")]
+unsafe fn squared_distance_avx_fma_unchecked(a: &[f32], b: &[f32]) -> f32 {
+    let dim = a.len();
+    // The vector loads only bounds-check their first lane, so bound `b` here
+    let b = &b[..dim];
+    let mut acc = [_mm256_setzero_ps(); 4];
+    let mut i = 0;
+
+    // Process 32 elements at a time across 4 accumulators
+    while i + 32 <= dim {
+        for (k, acc) in acc.iter_mut().enumerate() {
+            let d = _mm256_sub_ps(
+                _mm256_loadu_ps(&a[i + 8 * k]),
+                _mm256_loadu_ps(&b[i + 8 * k]),
+            );
+            *acc = _mm256_fmadd_ps(d, d, *acc);
+        }
+        i += 32;
+    }
+
+    // Process remaining full vectors 8 elements at a time
+    while i + 8 <= dim {
+        let d = _mm256_sub_ps(_mm256_loadu_ps(&a[i]), _mm256_loadu_ps(&b[i]));
+        acc[0] = _mm256_fmadd_ps(d, d, acc[0]);
+        i += 8;
+    }
+
+    let sum_vec = _mm256_add_ps(_mm256_add_ps(acc[0], acc[1]), _mm256_add_ps(acc[2], acc[3]));
+    hsum256_ps(sum_vec) + squared_distance_scalar(&a[i..], &b[i..])
+}
+
+// NEON squared euclidean distance using 128-bit registers, falling back to
+// scalar when the CPU does not support NEON
+#[cfg(target_arch = "aarch64")]
+pub fn squared_distance_neon(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() < 4 || !is_aarch64_feature_detected!("neon") {
+        return squared_distance_scalar(a, b);
+    }
+
+    unsafe {
+        let dim = a.len();
+        let b = &b[..dim];
+        let mut sum_vec = vdupq_n_f32(0.0);
+        let mut i = 0;
+
+        // Process 4 elements at a time using NEON
+        while i + 4 <= dim {
+            let d = vsubq_f32(vld1q_f32(&a[i]), vld1q_f32(&b[i]));
+            sum_vec = vfmaq_f32(sum_vec, d, d);
+            i += 4;
+        }
+
+        vaddvq_f32(sum_vec) + squared_distance_scalar(&a[i..], &b[i..])
+    }
+}
+
+// Rows of a tile scored together by `gemv_tiled`
+#[cfg(feature = "gemv")]
+const GEMV_ROWS: usize = 4;
+
+// Matrix-vector product of a row-major `matrix` with `num_columns` columns.
+// Four rows are scored per pass so each weight is loaded once per tile and the
+// four accumulations run as independent dependency chains. Every row is still
+// summed left to right from zero, so each result is bit-identical to `dot_scalar`.
+#[cfg(feature = "gemv")]
+pub fn gemv_tiled<F: Float>(matrix: &[F], vector: &[F], num_columns: usize) -> Vec<F> {
+    let mut out = Vec::with_capacity(matrix.len() / num_columns);
+    let mut tiles = matrix.chunks_exact(GEMV_ROWS * num_columns);
+    for tile in &mut tiles {
+        let (r0, rest) = tile.split_at(num_columns);
+        let (r1, rest) = rest.split_at(num_columns);
+        let (r2, r3) = rest.split_at(num_columns);
+        let mut acc = [F::ZERO; GEMV_ROWS];
+        for (j, &v) in vector.iter().enumerate() {
+            acc[0] = acc[0] + r0[j] * v;
+            acc[1] = acc[1] + r1[j] * v;
+            acc[2] = acc[2] + r2[j] * v;
+            acc[3] = acc[3] + r3[j] * v;
+        }
+        out.extend_from_slice(&acc);
+    }
+
+    // Handle the remaining rows one at a time
+    for row in tiles.remainder().chunks(num_columns) {
+        out.push(dot_scalar(vector, row));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ragged_inputs() -> impl Iterator<Item = (Vec<f32>, Vec<f32>)> {
+        [1, 3, 4, 7, 8, 15, 16, 17, 33, 100, 1027, 65539]
+            .into_iter()
+            .map(|dim| {
+                let weights = (0..dim).map(|i| (i as f32 * 0.37).sin()).collect();
+                let input = (0..dim).map(|i| (i as f32 * 0.11).cos()).collect();
+                (weights, input)
+            })
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn avx512_masked_tail_matches_scalar() {
+        for dim in 1..32 {
+            let weights: Vec<f32> = (0..dim).map(|i| (i as f32 * 0.37).sin()).collect();
+            let input: Vec<f32> = (0..dim).map(|i| (i as f32 * 0.11).cos()).collect();
+            let expected = dot_scalar(&weights, &input);
+            let got = dot_avx512(&weights, &input);
+            assert!(
+                (expected - got).abs() < 1e-5,
+                "dim {}: {} vs {}",
+                dim,
+                expected,
+                got
+            );
+        }
+        assert_eq!(dot_avx512(&[], &[]), 0.0);
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn x86_kernels_match_scalar_on_ragged_dims() {
+        for (weights, input) in ragged_inputs() {
+            let expected = dot_scalar(&weights, &input);
+            for got in [
+                dot_sse(&weights, &input),
+                dot_sse41(&weights, &input),
+                dot_avx(&weights, &input),
+                dot_avx2(&weights, &input),
+                dot_avx512(&weights, &input),
+            ] {
+                assert!((expected - got).abs() < 1e-4, "{} vs {}", expected, got);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn x86_squared_distances_match_scalar_on_ragged_dims() {
+        for (a, b) in ragged_inputs() {
+            let expected = squared_distance_scalar(&a, &b);
+            let mut got = vec![squared_distance_sse(&a, &b), squared_distance_avx(&a, &b)];
+            if is_x86_feature_detected!("avx") {
+                got.push(unsafe { squared_distance_avx_unchecked(&a, &b) });
+            }
+            if is_x86_feature_detected!("avx") && is_x86_feature_detected!("fma") {
+                got.push(unsafe { squared_distance_avx_fma_unchecked(&a, &b) });
+            }
+            // the sums grow with the dimension, so compare relative to the total
+            for got in got {
+                let error = (expected - got).abs() / expected.max(1.0);
+                assert!(error < 1e-4, "{} vs {}", expected, got);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "aarch64")]
+    fn neon_squared_distance_matches_scalar_on_ragged_dims() {
+        for (a, b) in ragged_inputs() {
+            let expected = squared_distance_scalar(&a, &b);
+            let got = squared_distance_neon(&a, &b);
+            let error = (expected - got).abs() / expected.max(1.0);
+            assert!(error < 1e-4, "{} vs {}", expected, got);
+        }
+    }
+
+    // Inputs shorter than a register must never reach the vector loads. The values
+    // are small multiples of 0.5, so every path sums them exactly.
+    #[test]
+    fn tiny_dims_match_scalar_exactly() {
+        for dim in [0, 1, 3, 5, 7] {
+            let a: Vec<f32> = (0..dim).map(|i| i as f32 * 0.5 - 1.0).collect();
+            let b: Vec<f32> = (0..dim).map(|i| 2.0 - i as f32).collect();
+            let dot = dot_scalar(&a, &b);
+            let distance = squared_distance_scalar(&a, &b);
+            #[cfg(target_arch = "x86_64")]
+            {
+                for got in [
+                    dot_sse(&a, &b),
+                    dot_sse41(&a, &b),
+                    dot_avx(&a, &b),
+                    dot_avx2(&a, &b),
+                    dot_avx512(&a, &b),
+                ] {
+                    assert_eq!(got, dot, "dim {}", dim);
+                }
+                assert_eq!(squared_distance_sse(&a, &b), distance, "dim {}", dim);
+                assert_eq!(squared_distance_avx(&a, &b), distance, "dim {}", dim);
+            }
+            #[cfg(target_arch = "aarch64")]
+            {
+                assert_eq!(dot_neon(&a, &b), dot, "dim {}", dim);
+                assert_eq!(squared_distance_neon(&a, &b), distance, "dim {}", dim);
+            }
+        }
+    }
+
+    // An input shorter than the weights, or `b` shorter than `a`, must panic on
+    // the reslice instead of letting a vector load read past its end
+    #[test]
+    fn short_inputs_panic() {
+        let weights = [0.5f32; 40];
+        let input = [1.0f32; 37];
+        #[cfg(target_arch = "x86_64")]
+        for kernel in [
+            dot_sse,
+            dot_sse41,
+            dot_avx,
+            dot_avx2,
+            dot_avx512,
+            squared_distance_sse,
+            squared_distance_avx,
+        ] {
+            assert!(std::panic::catch_unwind(|| kernel(&weights, &input)).is_err());
+        }
+        #[cfg(target_arch = "aarch64")]
+        for kernel in [dot_neon, squared_distance_neon] {
+            assert!(std::panic::catch_unwind(|| kernel(&weights, &input)).is_err());
+        }
+    }
+
+    // Slices starting 0 to 16 elements into a cache-line aligned buffer hit
+    // every mix of aligned and misaligned starts for each register width.
+    // Taking an aligned load on any of them would fault or trip a debug assertion.
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn x86_kernels_handle_misaligned_slices() {
+        use crate::models::aligned::AlignedVec;
+
+        let dim = 100;
+        let weights: AlignedVec<f32> = (0..dim + 16)
+            .map(|i| (i as f32 * 0.37).sin())
+            .collect::<Vec<_>>()
+            .into();
+        let input: AlignedVec<f32> = (0..dim + 16)
+            .map(|i| (i as f32 * 0.11).cos())
+            .collect::<Vec<_>>()
+            .into();
+        for w_offset in 0..=16 {
+            for x_offset in [0, 1, 4, 8, 16, w_offset] {
+                let w = &weights[w_offset..w_offset + dim];
+                let x = &input[x_offset..x_offset + dim];
+                let expected = dot_scalar(w, x);
+                for got in [
+                    dot_sse(w, x),
+                    dot_sse41(w, x),
+                    dot_avx(w, x),
+                    dot_avx2(w, x),
+                    dot_avx512(w, x),
+                ] {
+                    assert!(
+                        (expected - got).abs() < 1e-4,
+                        "offsets {} and {}: {} vs {}",
+                        w_offset,
+                        x_offset,
+                        expected,
+                        got
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn horizontal_sums_match_scalar() {
+        for seed in 0..50 {
+            let lanes: Vec<f32> = (0..8)
+                .map(|i| ((seed * 8 + i) as f32 * 1.618).sin() * 100.0)
+                .collect();
+            let sum4: f32 = lanes[..4].iter().sum();
+            let got4 = unsafe { hsum128_ps(_mm_loadu_ps(lanes.as_ptr())) };
+            assert!((sum4 - got4).abs() < 1e-3, "{} vs {}", sum4, got4);
+
+            if is_x86_feature_detected!("avx") {
+                let sum8: f32 = lanes.iter().sum();
+                let got8 = unsafe { hsum256_ps(_mm256_loadu_ps(lanes.as_ptr())) };
+                assert!((sum8 - got8).abs() < 1e-3, "{} vs {}", sum8, got8);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn avx_fma_matches_scalar_on_ragged_dims() {
+        if !is_x86_feature_detected!("fma") {
+            return;
+        }
+        for (weights, input) in ragged_inputs() {
+            let expected = dot_scalar(&weights, &input);
+            let got = unsafe { dot_avx_fma_unchecked(&weights, &input) };
+            assert!((expected - got).abs() < 1e-4, "{} vs {}", expected, got);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "portable_simd")]
+    fn portable_kernel_matches_scalar_on_ragged_dims() {
+        for (weights, input) in ragged_inputs() {
+            let expected = dot_scalar(&weights, &input);
+            let got = dot_portable(&weights, &input);
+            assert!((expected - got).abs() < 1e-4, "{} vs {}", expected, got);
+        }
+    }
+
+    #[test]
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    fn simd128_kernel_matches_scalar_on_ragged_dims() {
+        for (weights, input) in ragged_inputs() {
+            let expected = dot_scalar(&weights, &input);
+            let got = dot_simd128(&weights, &input);
+            assert!((expected - got).abs() < 1e-4, "{} vs {}", expected, got);
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "aarch64")]
+    fn neon_kernel_matches_scalar_on_ragged_dims() {
+        for (weights, input) in ragged_inputs() {
+            let expected = dot_scalar(&weights, &input);
+            let got = dot_neon(&weights, &input);
+            assert!((expected - got).abs() < 1e-4, "{} vs {}", expected, got);
+        }
+    }
+}
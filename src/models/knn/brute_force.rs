@@ -0,0 +1,253 @@
+// File: src/models/knn/brute_force.rs
+//
+// This file implements a brute-force k-nearest-neighbors classifier. Every query
+// computes the squared euclidean distance to all training points with the SIMD
+// distance kernels, keeps the k closest and takes a majority vote of their
+// labels. Ties go to the tied class with the nearest neighbor. The cost is
+// O(n * d) per query, which is fine for small reference sets.
+//
+// Parameter file layout (flat little-endian f32, same encoding as the other models):
+//
+//     [num_points, num_features, k,
+//      x_0[0] .. x_0[d-1], x_1[0] .. x_{n-1}[d-1],
+//      label_0 .. label_{n-1}]
+//
+// The counts, k and the labels are stored as f32 and must be exact non-negative
+// integers. Labels are class indices.
+
+use crate::models::error::{header_count, InferenceError};
+#[cfg(target_arch = "x86_64")]
+use crate::models::kernels::squared_distance_avx;
+#[cfg(target_arch = "aarch64")]
+use crate::models::kernels::squared_distance_neon;
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+use crate::models::kernels::squared_distance_scalar;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+// Number of header values preceding the training points
+const HEADER_LEN: usize = 3;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Knn {
+    points: Vec<Vec<f32>>,
+    labels: Vec<usize>,
+    num_classes: usize,
+    k: usize,
+}
+
+impl Knn {
+    pub fn new(points: Vec<Vec<f32>>, labels: Vec<usize>, k: usize) -> Self {
+        assert!(
+            !points.is_empty(),
+            "At least one training point is required"
+        );
+        assert_eq!(
+            points.len(),
+            labels.len(),
+            "One label per training point is required"
+        );
+        assert!(
+            points.iter().all(|point| point.len() == points[0].len()),
+            "All training points must have the same dimension"
+        );
+        assert!(
+            (1..=points.len()).contains(&k),
+            "k must be between 1 and the number of training points"
+        );
+        let num_classes = labels.iter().max().map_or(0, |&label| label + 1);
+        Self {
+            points,
+            labels,
+            num_classes,
+            k,
+        }
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.num_classes
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.points[0].len()
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the class with the most votes among the k nearest neighbors
+    pub fn predict(&self, input: &[f32]) -> usize {
+        self.try_predict(input).unwrap()
+    }
+
+    pub fn try_predict(&self, input: &[f32]) -> Result<usize, InferenceError> {
+        let nearest = self.nearest(input)?;
+        let votes = self.votes(&nearest);
+        // Walking the neighbors nearest first, a class only takes over with strictly
+        // more votes, so ties go to the class seen first
+        let mut best = self.labels[nearest[0]];
+        for &index in &nearest {
+            let class = self.labels[index];
+            if votes[class] > votes[best] {
+                best = class;
+            }
+        }
+        Ok(best)
+    }
+
+    /// Returns the share of the k nearest neighbors voting for each class
+    pub fn predict_proba(&self, input: &[f32]) -> Vec<f32> {
+        self.try_predict_proba(input).unwrap()
+    }
+
+    pub fn try_predict_proba(&self, input: &[f32]) -> Result<Vec<f32>, InferenceError> {
+        Ok(self
+            .votes(&self.nearest(input)?)
+            .into_iter()
+            .map(|count| count as f32 / self.k as f32)
+            .collect())
+    }
+
+    /// Indices of the k nearest training points, nearest first. Equal distances
+    /// are ordered by index.
+    pub fn nearest(&self, input: &[f32]) -> Result<Vec<usize>, InferenceError> {
+        if self.num_features() != input.len() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: self.num_features(),
+                got: input.len(),
+            });
+        }
+        let mut distances: Vec<(f32, usize)> = self
+            .points
+            .iter()
+            .map(|point| squared_distance(point, input))
+            .zip(0..)
+            .collect();
+        let by_distance =
+            |a: &(f32, usize), b: &(f32, usize)| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1));
+        distances.select_nth_unstable_by(self.k - 1, by_distance);
+        distances.truncate(self.k);
+        distances.sort_unstable_by(by_distance);
+        Ok(distances.into_iter().map(|(_, index)| index).collect())
+    }
+
+    // Number of neighbors labelled with each class
+    fn votes(&self, nearest: &[usize]) -> Vec<usize> {
+        let mut votes = vec![0; self.num_classes];
+        for &index in nearest {
+            votes[self.labels[index]] += 1;
+        }
+        votes
+    }
+
+    pub fn predict_batch(&self, data: &[f32], num_features: usize) -> Vec<usize> {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.chunks(num_features)
+            .map(|chunk| self.predict(chunk))
+            .collect()
+    }
+
+    /// Same as `predict_batch` but spreads samples across the rayon thread pool
+    #[cfg(feature = "rayon")]
+    pub fn predict_batch_parallel(&self, data: &[f32], num_features: usize) -> Vec<usize> {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.par_chunks(num_features)
+            .map(|chunk| self.predict(chunk))
+            .collect()
+    }
+
+    /// Builds a model from a flat parameter buffer using the layout documented above
+    pub fn from_params(params: &[f32]) -> Result<Self, InferenceError> {
+        if params.len() < HEADER_LEN {
+            return Err(InferenceError::InvalidParameters(format!(
+                "expected a {} value header, found {} values",
+                HEADER_LEN,
+                params.len()
+            )));
+        }
+        let num_points = header_count(params[0], "number of training points")?;
+        let num_features = header_count(params[1], "number of features")?;
+        let k = header_count(params[2], "k")?;
+
+        let table = num_points * num_features;
+        let expected = HEADER_LEN + table + num_points;
+        if num_points == 0 || num_features == 0 || params.len() != expected {
+            return Err(InferenceError::InvalidParameters(format!(
+                "{} training points of dimension {} need {} values, found {}",
+                num_points,
+                num_features,
+                expected,
+                params.len()
+            )));
+        }
+        if k == 0 || k > num_points {
+            return Err(InferenceError::InvalidParameters(format!(
+                "k must be between 1 and the {} training points, found {}",
+                num_points, k
+            )));
+        }
+
+        let points = params[HEADER_LEN..HEADER_LEN + table]
+            .chunks(num_features)
+            .map(|row| row.to_vec())
+            .collect();
+        let labels = params[HEADER_LEN + table..]
+            .iter()
+            .map(|&label| header_count(label, "label"))
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(points, labels, k))
+    }
+}
+
+// Fastest distance kernel for the target. The x86 kernel picks AVX with or
+// without FMA, or SSE, at runtime.
+#[inline]
+fn squared_distance(a: &[f32], b: &[f32]) -> f32 {
+    #[cfg(target_arch = "x86_64")]
+    return squared_distance_avx(a, b);
+    #[cfg(target_arch = "aarch64")]
+    return squared_distance_neon(a, b);
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    squared_distance_scalar(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn majority_of_nearest_neighbors() {
+        // class 0 around the origin, class 1 around (5, 5), k = 3
+        #[rustfmt::skip]
+        let params = [
+            5.0, 2.0, 3.0,
+            0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 5.0, 5.0, 4.0, 5.0,
+            0.0, 0.0, 0.0, 1.0, 1.0,
+        ];
+        let model = Knn::from_params(&params).unwrap();
+        assert_eq!(model.num_classes(), 2);
+        assert_eq!(model.nearest(&[0.9, 0.1]).unwrap(), vec![1, 0, 2]);
+        assert_eq!(model.predict_batch(&[0.5, 0.5, 4.0, 4.0], 2), vec![0, 1]);
+        // (3, 3) is nearest to the class 1 points at (4, 5) and (5, 5)
+        assert_eq!(model.predict_proba(&[3.0, 3.0]), vec![1.0 / 3.0, 2.0 / 3.0]);
+
+        // with k = 2 and one vote each, the nearest neighbor decides
+        let model = Knn::new(vec![vec![0.0], vec![2.0]], vec![0, 1], 2);
+        assert_eq!(model.predict(&[1.5]), 1);
+        assert_eq!(model.predict(&[0.5]), 0);
+
+        let mut bad_k = params;
+        bad_k[2] = 6.0;
+        assert!(Knn::from_params(&bad_k).is_err());
+        assert!(Knn::from_params(&params[..17]).is_err());
+    }
+}
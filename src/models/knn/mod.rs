@@ -0,0 +1 @@
+pub mod brute_force;
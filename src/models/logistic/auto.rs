@@ -0,0 +1,347 @@
+// File: src/models/logistic/auto.rs
+//
+// This file implements a strategy that picks the best available scoring
+// implementation at runtime. Detection happens once when the strategy is
+// constructed so that per-sample inference only pays for a single branch.
+//
+// `for_feature_dim` also weighs the model size. Measured ns per sample on a
+// Xeon with AVX-512 (benches/strategies.rs, group "crossover"):
+//
+//     features  sequential    sse   avx2  avx512
+//            4        1.7     6.1    6.5     4.2
+//           16        4.8     7.2    7.0     4.4
+//           32        9.3     8.2    7.0     5.4
+//          128       48.9    21.9   13.0     9.3
+//          512      265.5    87.9   59.4    54.8
+//          768      440.8   136.9   93.7    96.6
+//         4096     2523.1   730.2  524.0   542.4
+//
+// The register setup costs more than a handful of multiplies, so short models
+// stay scalar; past ~600 features the loop is bound by memory and AVX-512 loses
+// to AVX2 on this part. SSE never won here, so its threshold matches AVX2's.
+
+use crate::error::Error;
+#[cfg(target_arch = "x86_64")]
+use crate::models::kernels::{dot_avx_fma_unchecked, dot_avx_unchecked};
+use crate::models::logistic::base::OptimizationStrategy;
+#[cfg(target_arch = "aarch64")]
+use crate::models::strategy::NEON;
+use crate::models::strategy::{ScoreStrategy, Sequential};
+#[cfg(target_arch = "x86_64")]
+use crate::models::strategy::{AVX512, SSE};
+use crate::utils::{best_simd, is_simd_supported, SimdInstructionSet};
+
+// Forward implementation selected at construction time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dispatch {
+    Sequential,
+    #[cfg(target_arch = "x86_64")]
+    Sse,
+    #[cfg(target_arch = "x86_64")]
+    Avx,
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+    #[cfg(target_arch = "x86_64")]
+    Avx512,
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+}
+
+/// Feature dimensions at which each register width starts to pay off. The
+/// defaults come from the table at the top of this file; re-run the
+/// "crossover" benchmark group to tune them for another CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimThresholds {
+    /// Smallest dimension for the 128-bit paths (SSE, NEON)
+    pub min_128: usize,
+    /// Smallest dimension for the 256-bit paths (AVX, AVX2)
+    pub min_256: usize,
+    /// Smallest dimension for AVX-512
+    pub min_512: usize,
+    /// Largest dimension for AVX-512; wider models step down to AVX2
+    pub max_512: usize,
+}
+
+impl Default for DimThresholds {
+    fn default() -> Self {
+        Self {
+            min_128: 32,
+            min_256: 32,
+            min_512: 16,
+            max_512: 640,
+        }
+    }
+}
+
+// Runtime dispatched implementation
+#[derive(Debug, Clone, Copy)]
+pub struct Auto {
+    dispatch: Dispatch,
+    // Whether the AVX and AVX2 paths use the fused multiply-add kernel
+    #[cfg(target_arch = "x86_64")]
+    fma: bool,
+}
+
+impl Auto {
+    /// Detects the SIMD instruction set of the running CPU and selects the
+    /// matching forward implementation
+    pub fn new() -> Self {
+        let simd = best_simd();
+        Self::from_instruction_set(simd)
+    }
+
+    /// Selects the implementation for `simd`, failing when the running CPU
+    /// cannot execute it
+    pub fn try_from_instruction_set(simd: SimdInstructionSet) -> Result<Self, Error> {
+        if !is_simd_supported(simd) {
+            return Err(Error::UnsupportedSimd(simd));
+        }
+        Ok(Self::from_instruction_set(simd))
+    }
+
+    /// Like `new`, but uses AVX2 on CPUs with AVX-512. Worth measuring on parts
+    /// whose clock drops under 512-bit instructions: for small batches AVX2 can
+    /// win, while long runs over wide models usually still favour AVX-512.
+    pub fn prefer_avx2() -> Self {
+        let simd = best_simd();
+        Self::from_instruction_set(simd.without_avx512())
+    }
+
+    /// Like `new`, but only uses a register width when `feature_dim` is in the
+    /// range where it measured fastest, per the default `DimThresholds`
+    pub fn for_feature_dim(feature_dim: usize) -> Self {
+        let simd = best_simd();
+        Self::sized(simd, feature_dim, DimThresholds::default())
+    }
+
+    /// Steps down from the best path for `simd` until `feature_dim` falls
+    /// inside the selected width's thresholds
+    pub fn sized(simd: SimdInstructionSet, feature_dim: usize, thresholds: DimThresholds) -> Self {
+        let simd = if (thresholds.min_512..=thresholds.max_512).contains(&feature_dim) {
+            simd
+        } else {
+            simd.without_avx512()
+        };
+        let mut auto = Self::from_instruction_set(simd);
+        #[cfg(target_arch = "x86_64")]
+        if auto.lanes() == 8 && feature_dim < thresholds.min_256 {
+            auto.dispatch = Dispatch::Sse;
+        }
+        if auto.lanes() == 4 && feature_dim < thresholds.min_128 {
+            auto.dispatch = Dispatch::Sequential;
+        }
+        auto
+    }
+
+    /// Selects the forward implementation for an already detected instruction set,
+    /// falling back to `Sequential` when no supported SIMD path exists
+    pub fn from_instruction_set(simd: SimdInstructionSet) -> Self {
+        let dispatch = match simd {
+            #[cfg(target_arch = "x86_64")]
+            SimdInstructionSet::AVX512 => Dispatch::Avx512,
+            #[cfg(target_arch = "x86_64")]
+            SimdInstructionSet::AVX2 => Dispatch::Avx2,
+            #[cfg(target_arch = "x86_64")]
+            SimdInstructionSet::AVX => Dispatch::Avx,
+            #[cfg(target_arch = "x86_64")]
+            SimdInstructionSet::SSE4_2 | SimdInstructionSet::SSE4_1 | SimdInstructionSet::SSE2 => {
+                Dispatch::Sse
+            }
+            #[cfg(target_arch = "aarch64")]
+            SimdInstructionSet::Neon => Dispatch::Neon,
+            #[allow(unreachable_patterns)]
+            _ => Dispatch::Sequential,
+        };
+        // The AVX and AVX2 paths call their kernels without checking the CPU again,
+        // so settle AVX and FMA support here
+        #[cfg(target_arch = "x86_64")]
+        let dispatch = match dispatch {
+            Dispatch::Avx | Dispatch::Avx2 if !is_x86_feature_detected!("avx") => Dispatch::Sse,
+            dispatch => dispatch,
+        };
+        Self {
+            dispatch,
+            #[cfg(target_arch = "x86_64")]
+            fma: matches!(dispatch, Dispatch::Avx | Dispatch::Avx2)
+                && is_x86_feature_detected!("fma"),
+        }
+    }
+
+    /// f32 lanes per register of the selected path, the multiple to pad the
+    /// feature dimension to with `pad_to_lanes`
+    pub fn lanes(&self) -> usize {
+        match self.dispatch {
+            Dispatch::Sequential => 1,
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Sse => 4,
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Avx | Dispatch::Avx2 => 8,
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Avx512 => 16,
+            #[cfg(target_arch = "aarch64")]
+            Dispatch::Neon => 4,
+        }
+    }
+
+    /// Instruction set of the selected path
+    pub fn instruction_set(&self) -> SimdInstructionSet {
+        match self.dispatch {
+            Dispatch::Sequential => SimdInstructionSet::None,
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Sse => SimdInstructionSet::SSE2,
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Avx => SimdInstructionSet::AVX,
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Avx2 => SimdInstructionSet::AVX2,
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Avx512 => SimdInstructionSet::AVX512,
+            #[cfg(target_arch = "aarch64")]
+            Dispatch::Neon => SimdInstructionSet::Neon,
+        }
+    }
+
+    /// Returns true when no SIMD path was selected
+    pub fn is_sequential(&self) -> bool {
+        self.dispatch == Dispatch::Sequential
+    }
+}
+
+impl Default for Auto {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScoreStrategy for Auto {
+    #[inline]
+    fn score(&self, weights: &[f32], input: &[f32], bias: f32) -> f32 {
+        match self.dispatch {
+            Dispatch::Sequential => Sequential.score(weights, input, bias),
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Sse => SSE.score(weights, input, bias),
+            // SAFETY: `from_instruction_set` only keeps these paths on a CPU with AVX,
+            // and only sets `fma` when it also has FMA
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Avx | Dispatch::Avx2 if self.fma => unsafe {
+                dot_avx_fma_unchecked(weights, input) + bias
+            },
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Avx | Dispatch::Avx2 => unsafe { dot_avx_unchecked(weights, input) + bias },
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Avx512 => AVX512.score(weights, input, bias),
+            #[cfg(target_arch = "aarch64")]
+            Dispatch::Neon => NEON.score(weights, input, bias),
+        }
+    }
+
+    // Dispatches once per batch rather than once per sample
+    fn score_batch(
+        &self,
+        weights: &[f32],
+        data: &[f32],
+        bias: f32,
+        num_features: usize,
+    ) -> Vec<f32> {
+        match self.dispatch {
+            Dispatch::Sequential => Sequential.score_batch(weights, data, bias, num_features),
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Sse => SSE.score_batch(weights, data, bias, num_features),
+            // SAFETY: as in `score`
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Avx | Dispatch::Avx2 if self.fma => data
+                .chunks(num_features)
+                .map(|row| unsafe { dot_avx_fma_unchecked(weights, row) } + bias)
+                .collect(),
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Avx | Dispatch::Avx2 => data
+                .chunks(num_features)
+                .map(|row| unsafe { dot_avx_unchecked(weights, row) } + bias)
+                .collect(),
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Avx512 => AVX512.score_batch(weights, data, bias, num_features),
+            #[cfg(target_arch = "aarch64")]
+            Dispatch::Neon => NEON.score_batch(weights, data, bias, num_features),
+        }
+    }
+}
+
+// Every dispatched scorer uses the exact sigmoid
+impl OptimizationStrategy for Auto {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_back_to_sequential() {
+        let auto = Auto::from_instruction_set(SimdInstructionSet::None);
+        assert!(auto.is_sequential());
+
+        let weights = [0.5, -1.0, 2.0];
+        let input = [1.0, 2.0, 3.0];
+        assert_eq!(
+            auto.forward(&weights, &input, 0.25),
+            Sequential.forward(&weights, &input, 0.25)
+        );
+        assert_eq!(auto.instruction_set(), SimdInstructionSet::None);
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn sized_steps_down_outside_the_thresholds() {
+        let thresholds = DimThresholds::default();
+        let pick = |simd, dim| Auto::sized(simd, dim, thresholds).instruction_set();
+        assert_eq!(
+            pick(SimdInstructionSet::AVX512, 8),
+            SimdInstructionSet::None
+        );
+        assert_eq!(
+            pick(SimdInstructionSet::AVX512, 16),
+            SimdInstructionSet::AVX512
+        );
+        assert_eq!(
+            pick(SimdInstructionSet::AVX512, 4096),
+            SimdInstructionSet::AVX2
+        );
+        assert_eq!(pick(SimdInstructionSet::AVX2, 16), SimdInstructionSet::None);
+        assert_eq!(pick(SimdInstructionSet::AVX2, 64), SimdInstructionSet::AVX2);
+
+        let sse_band = DimThresholds {
+            min_128: 8,
+            ..thresholds
+        };
+        let auto = Auto::sized(SimdInstructionSet::AVX2, 16, sse_band);
+        assert_eq!(auto.instruction_set(), SimdInstructionSet::SSE2);
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn prefer_avx2_steps_down_from_avx512() {
+        let auto = Auto::from_instruction_set(SimdInstructionSet::AVX512.without_avx512());
+        assert_eq!(auto.instruction_set(), SimdInstructionSet::AVX2);
+        assert_ne!(
+            Auto::prefer_avx2().instruction_set(),
+            SimdInstructionSet::AVX512
+        );
+        assert_eq!(
+            SimdInstructionSet::SSE2.without_avx512(),
+            SimdInstructionSet::SSE2
+        );
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn avx_paths_match_sequential() {
+        let weights: Vec<f32> = (0..37).map(|i| (i as f32 * 0.37).sin()).collect();
+        let data: Vec<f32> = (0..74).map(|i| (i as f32 * 0.11).cos()).collect();
+        let expected = Sequential.score_batch(&weights, &data, 0.5, 37);
+        for simd in [SimdInstructionSet::AVX, SimdInstructionSet::AVX2] {
+            let auto = Auto::from_instruction_set(simd);
+            let batch = auto.score_batch(&weights, &data, 0.5, 37);
+            for (row, (&expected, &got)) in data.chunks(37).zip(expected.iter().zip(&batch)) {
+                assert!((expected - got).abs() < 1e-4, "{} vs {}", expected, got);
+                assert_eq!(auto.score(&weights, row, 0.5), got);
+            }
+        }
+    }
+}
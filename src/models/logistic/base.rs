@@ -19,38 +19,475 @@
 // #[cfg(target_arch = "aarch64")]
 // use std::arch::aarch64 as arch;
 
-// Trait for different optimization strategies
-pub trait OptimizationStrategy {
-    fn forward(&self, weights: &[f32], input: &[f32], bias: f32) -> f32;
-}
+use crate::models::aligned::AlignedVec;
+use crate::models::error::InferenceError;
+use crate::models::float::Float;
+use crate::models::kernels::dot_scalar;
+use crate::models::logistic::activation::sigmoid;
+use crate::models::logistic::sparse::SparseStrategy;
+use crate::models::strategy::ScoreStrategy;
+pub use crate::models::strategy::Sequential;
+use crate::utils::io::{read_params, BiasPosition};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::io;
+use std::path::Path;
+
+// Trait for different optimization strategies. The linear score comes from the
+// shared `ScoreStrategy`; this adds the activation that turns it into a probability.
+pub trait OptimizationStrategy<F: Float = f32>: ScoreStrategy<F> {
+    // Activation applied to the score, the exact sigmoid unless overridden
+    fn activate(&self, logit: F) -> F {
+        sigmoid(logit)
+    }
 
-// Basic sequential implementation
-pub struct Sequential;
-impl OptimizationStrategy for Sequential {
-    fn forward(&self, weights: &[f32], input: &[f32], bias: f32) -> f32 {
-        let dot_product: f32 = weights.iter().zip(input.iter()).map(|(w, x)| w * x).sum();
-        1.0 / (1.0 + (-dot_product - bias).exp())
+    fn forward(&self, weights: &[F], input: &[F], bias: F) -> F {
+        self.activate(self.score(weights, input, bias))
+    }
+
+    // Forward pass over a row-major block of samples. The default activates
+    // `score_batch`; override it to vectorize the activation as well.
+    fn forward_batch(&self, weights: &[F], data: &[F], bias: F, num_features: usize) -> Vec<F> {
+        self.score_batch(weights, data, bias, num_features)
+            .into_iter()
+            .map(|logit| self.activate(logit))
+            .collect()
     }
 }
 
-// Main logistic regression struct that can use different optimization strategies
-pub struct LogisticRegression<T: OptimizationStrategy> {
-    weights: Vec<f32>,
-    bias: f32,
+impl<F: Float> OptimizationStrategy<F> for Sequential {}
+
+// Main logistic regression struct that can use different optimization strategies.
+// With the `serde` feature the strategy is not serialized; deserializing rebuilds
+// it from `Default`, so `Auto` re-detects the CPU of the machine loading the model.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogisticRegression<F: Float, T: OptimizationStrategy<F>> {
+    // Cache-line aligned so SIMD loads of the weights never straddle two lines
+    weights: AlignedVec<F>,
+    bias: F,
+    #[cfg_attr(feature = "serde", serde(skip))]
     strategy: T,
+    // Standardization the weights were built with, kept so the model carries its preprocessing
+    feature_means: Option<Vec<F>>,
+    feature_stds: Option<Vec<F>>,
 }
 
-impl<T: OptimizationStrategy> LogisticRegression<T> {
-    pub fn new(weights: Vec<f32>, bias: f32, strategy: T) -> Self {
+impl<F: Float, T: OptimizationStrategy<F>> LogisticRegression<F, T> {
+    pub fn new(weights: Vec<F>, bias: F, strategy: T) -> Self {
         Self {
-            weights,
+            weights: weights.into(),
             bias,
             strategy,
+            feature_means: None,
+            feature_stds: None,
+        }
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn weights(&self) -> &[F] {
+        &self.weights
+    }
+
+    pub fn bias(&self) -> F {
+        self.bias
+    }
+
+    pub fn feature_means(&self) -> Option<&[F]> {
+        self.feature_means.as_deref()
+    }
+
+    pub fn feature_stds(&self) -> Option<&[F]> {
+        self.feature_stds.as_deref()
+    }
+
+    pub fn strategy(&self) -> &T {
+        &self.strategy
+    }
+
+    /// Copy of this model running another strategy, e.g. a scalar reference to
+    /// check a SIMD strategy against
+    pub fn with_strategy<U: OptimizationStrategy<F>>(
+        &self,
+        strategy: U,
+    ) -> LogisticRegression<F, U> {
+        LogisticRegression {
+            weights: self.weights.clone(),
+            bias: self.bias,
+            strategy,
+            feature_means: self.feature_means.clone(),
+            feature_stds: self.feature_stds.clone(),
+        }
+    }
+
+    /// Zero-pads the weights to the next multiple of `lanes` (8 for AVX, 16 for
+    /// AVX-512) so the SIMD kernels never reach their scalar tail. The zero
+    /// weights leave every score unchanged, but inputs must be padded to the new
+    /// `num_features` as well, once when they are loaded.
+    pub fn pad_to_lanes(mut self, lanes: usize) -> Self {
+        let width = self.weights.len().next_multiple_of(lanes.max(1));
+        let mut weights = self.weights.to_vec();
+        weights.resize(width, F::ZERO);
+        self.weights = weights.into();
+        if let Some(means) = &mut self.feature_means {
+            means.resize(width, F::ZERO);
+        }
+        if let Some(stds) = &mut self.feature_stds {
+            stds.resize(width, F::ONE);
+        }
+        self
+    }
+
+    pub fn predict(&self, input: &[F]) -> F {
+        self.try_predict(input).unwrap()
+    }
+
+    pub fn try_predict(&self, input: &[F]) -> Result<F, InferenceError> {
+        if self.weights.len() != input.len() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: self.weights.len(),
+                got: input.len(),
+            });
+        }
+        Ok(self.strategy.forward(&self.weights, input, self.bias))
+    }
+
+    /// Raw logit w . x + b before the activation, with any clamping the
+    /// strategy applies to it
+    pub fn logit(&self, input: &[F]) -> F {
+        self.try_logit(input).unwrap()
+    }
+
+    pub fn try_logit(&self, input: &[F]) -> Result<F, InferenceError> {
+        if self.weights.len() != input.len() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: self.weights.len(),
+                got: input.len(),
+            });
+        }
+        Ok(self.strategy.score(&self.weights, input, self.bias))
+    }
+
+    pub fn logit_batch(&self, data: &[F], num_features: usize) -> Vec<F> {
+        assert_eq!(
+            num_features,
+            self.weights.len(),
+            "Sample width does not match the model"
+        );
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        self.strategy
+            .score_batch(&self.weights, data, self.bias, num_features)
+    }
+
+    pub fn predict_batch(&self, data: &[F], num_features: usize) -> Vec<F> {
+        assert_eq!(
+            num_features,
+            self.weights.len(),
+            "Sample width does not match the model"
+        );
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        self.strategy
+            .forward_batch(&self.weights, data, self.bias, num_features)
+    }
+
+    /// Same as `predict_batch` but returns an error instead of panicking when
+    /// the sample width differs from the model or the last sample is partial
+    pub fn try_predict_batch(
+        &self,
+        data: &[F],
+        num_features: usize,
+    ) -> Result<Vec<F>, InferenceError> {
+        if num_features != self.weights.len() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: self.weights.len(),
+                got: num_features,
+            });
+        }
+        let partial = data.len() % num_features;
+        if partial != 0 {
+            return Err(InferenceError::DimensionMismatch {
+                expected: num_features,
+                got: partial,
+            });
+        }
+        Ok(self.predict_batch(data, num_features))
+    }
+
+    /// Same as `predict_batch` but spreads samples across the rayon thread pool.
+    /// The strategy is shared between threads so it must be `Sync`.
+    #[cfg(feature = "rayon")]
+    pub fn predict_batch_parallel(&self, data: &[F], num_features: usize) -> Vec<F>
+    where
+        F: Send + Sync,
+        T: Sync,
+    {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.par_chunks(num_features)
+            .map(|chunk| self.predict(chunk))
+            .collect()
+    }
+
+    /// Same as `logit_batch` but spreads samples across the rayon thread pool
+    #[cfg(feature = "rayon")]
+    pub fn logit_batch_parallel(&self, data: &[F], num_features: usize) -> Vec<F>
+    where
+        F: Send + Sync,
+        T: Sync,
+    {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.par_chunks(num_features)
+            .map(|chunk| self.logit(chunk))
+            .collect()
+    }
+}
+
+impl<F: Float, T: SparseStrategy<F>> LogisticRegression<F, T> {
+    /// Predicts from (feature index, value) pairs, touching only those features
+    pub fn predict_sparse(&self, input: &[(u32, F)]) -> F {
+        self.try_predict_sparse(input).unwrap()
+    }
+
+    pub fn try_predict_sparse(&self, input: &[(u32, F)]) -> Result<F, InferenceError> {
+        if let Some(&(index, _)) = input
+            .iter()
+            .find(|&&(index, _)| index as usize >= self.weights.len())
+        {
+            return Err(InferenceError::FeatureIndexOutOfRange {
+                index: index as usize,
+                num_features: self.weights.len(),
+            });
+        }
+        Ok(self
+            .strategy
+            .forward_sparse(&self.weights, input, self.bias))
+    }
+}
+
+// Builds a LogisticRegression whose inputs are standardized as (x - mean) / std.
+//
+// The normalization is folded into the weights and bias when the model is built:
+//
+//     w . ((x - mean) / std) + b = (w / std) . x + (b - sum_i w_i * mean_i / std_i)
+//
+// so every strategy runs its usual dot product on raw inputs with no extra pass
+// or allocation per sample.
+pub struct LogisticRegressionBuilder<F: Float> {
+    weights: Vec<F>,
+    bias: F,
+    feature_means: Option<Vec<F>>,
+    feature_stds: Option<Vec<F>>,
+}
+
+impl<F: Float> LogisticRegressionBuilder<F> {
+    pub fn new(weights: Vec<F>, bias: F) -> Self {
+        Self {
+            weights,
+            bias,
+            feature_means: None,
+            feature_stds: None,
         }
     }
 
-    pub fn predict(&self, input: &[f32]) -> f32 {
-        assert_eq!(self.weights.len(), input.len(), "Input dimension mismatch");
-        self.strategy.forward(&self.weights, input, self.bias)
+    pub fn feature_means(mut self, means: Vec<F>) -> Self {
+        self.feature_means = Some(means);
+        self
+    }
+
+    pub fn feature_stds(mut self, stds: Vec<F>) -> Self {
+        self.feature_stds = Some(stds);
+        self
+    }
+
+    pub fn build<T: OptimizationStrategy<F>>(
+        self,
+        strategy: T,
+    ) -> Result<LogisticRegression<F, T>, InferenceError> {
+        let num_features = self.weights.len();
+        for stats in [&self.feature_means, &self.feature_stds]
+            .into_iter()
+            .flatten()
+        {
+            if stats.len() != num_features {
+                return Err(InferenceError::DimensionMismatch {
+                    expected: num_features,
+                    got: stats.len(),
+                });
+            }
+        }
+        if let Some(stds) = &self.feature_stds {
+            // also rejects NaN, which fails every comparison
+            if !stds.iter().all(|&std| std > F::ZERO) {
+                return Err(InferenceError::InvalidParameters(
+                    "feature standard deviations must be positive".to_string(),
+                ));
+            }
+        }
+
+        let mut weights = self.weights;
+        let mut bias = self.bias;
+        if let Some(stds) = &self.feature_stds {
+            for (w, &std) in weights.iter_mut().zip(stds) {
+                *w = *w / std;
+            }
+        }
+        if let Some(means) = &self.feature_means {
+            bias = bias - dot_scalar(&weights, means);
+        }
+
+        Ok(LogisticRegression {
+            weights: weights.into(),
+            bias,
+            strategy,
+            feature_means: self.feature_means,
+            feature_stds: self.feature_stds,
+        })
+    }
+}
+
+impl<T: OptimizationStrategy> LogisticRegression<f32, T> {
+    /// Loads a model from a raw little-endian f32 file holding the weights and
+    /// the bias at `bias_position`
+    pub fn from_param_file(
+        path: &Path,
+        bias_position: BiasPosition,
+        strategy: T,
+    ) -> io::Result<Self> {
+        let (weights, bias) = read_params(path, bias_position)?;
+        Ok(Self::new(weights, bias, strategy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_predict_reports_dimension_mismatch() {
+        let model = LogisticRegression::new(vec![1.0, 2.0, 3.0], 0.0, Sequential);
+        assert_eq!(
+            model.try_predict(&[1.0, 2.0]),
+            Err(InferenceError::DimensionMismatch {
+                expected: 3,
+                got: 2
+            })
+        );
+        assert!(model.try_predict(&[0.0, 0.0, 0.0]).is_ok());
+        assert_eq!(
+            model.try_predict_batch(&[1.0; 7], 3),
+            Err(InferenceError::DimensionMismatch {
+                expected: 3,
+                got: 1
+            })
+        );
+        assert!(model.try_predict_batch(&[1.0; 6], 2).is_err());
+        assert_eq!(model.try_predict_batch(&[0.0; 6], 3), Ok(vec![0.5, 0.5]));
+    }
+
+    #[test]
+    fn with_strategy_keeps_parameters() {
+        let model = LogisticRegressionBuilder::new(vec![0.5, -1.0], 0.25)
+            .feature_means(vec![1.0, 2.0])
+            .build(Sequential)
+            .unwrap();
+        let copy = model.with_strategy(crate::models::logistic::auto::Auto::new());
+        assert_eq!(copy.feature_means(), Some(&[1.0, 2.0][..]));
+        assert_eq!(copy.predict(&[3.0, -1.0]), model.predict(&[3.0, -1.0]));
+    }
+
+    #[test]
+    fn sequential_runs_in_double_precision() {
+        let model: LogisticRegression<f64, Sequential> =
+            LogisticRegression::new(vec![0.5, -0.25], 0.125, Sequential);
+        let expected = 1.0 / (1.0 + (-(0.5 * 2.0 - 0.25 * 4.0 + 0.125f64)).exp());
+        assert_eq!(model.predict(&[2.0, 4.0]), expected);
+    }
+
+    #[test]
+    fn logit_is_the_score_before_the_sigmoid() {
+        let model = LogisticRegression::new(vec![0.5f32, -0.25], 0.125, Sequential);
+        assert_eq!(model.logit(&[2.0, 4.0]), 0.125);
+        assert_eq!(model.predict(&[2.0, 4.0]), sigmoid(0.125));
+        assert_eq!(
+            model.logit_batch(&[2.0, 4.0, 0.0, 0.0], 2),
+            vec![0.125, 0.125]
+        );
+        assert!(model.try_logit(&[1.0]).is_err());
+    }
+
+    #[test]
+    fn padding_to_lanes_keeps_predictions() {
+        let weights: Vec<f32> = (0..13).map(|i| i as f32 * 0.1 - 0.6).collect();
+        let input: Vec<f32> = (0..13).map(|i| 1.0 - i as f32 * 0.15).collect();
+        let model = LogisticRegressionBuilder::new(weights.clone(), 0.3)
+            .feature_stds(vec![2.0; 13])
+            .build(Sequential)
+            .unwrap();
+        let padded = LogisticRegressionBuilder::new(weights, 0.3)
+            .feature_stds(vec![2.0; 13])
+            .build(Sequential)
+            .unwrap()
+            .pad_to_lanes(8);
+        assert_eq!(padded.num_features(), 16);
+        assert_eq!(padded.feature_stds().map(<[f32]>::len), Some(16));
+
+        let mut padded_input = input.clone();
+        padded_input.resize(16, 0.0);
+        assert_eq!(padded.predict(&padded_input), model.predict(&input));
+        assert!(padded.try_predict(&input).is_err());
+        assert_eq!(padded.pad_to_lanes(8).num_features(), 16);
+    }
+
+    #[test]
+    fn builder_standardizes_inputs() {
+        let (weights, bias) = (vec![0.5f64, -1.5, 2.0], 0.25);
+        let (means, stds) = (vec![1.0, -2.0, 0.5], vec![2.0, 0.5, 4.0]);
+        let model = LogisticRegressionBuilder::new(weights.clone(), bias)
+            .feature_means(means.clone())
+            .feature_stds(stds.clone())
+            .build(Sequential)
+            .unwrap();
+        let reference = LogisticRegression::new(weights, bias, Sequential);
+
+        let input = [3.0, 1.0, -2.0];
+        let standardized: Vec<f64> = (0..3).map(|i| (input[i] - means[i]) / stds[i]).collect();
+        assert!((model.predict(&input) - reference.predict(&standardized)).abs() < 1e-12);
+
+        let invalid = LogisticRegressionBuilder::new(vec![1.0f32], 0.0)
+            .feature_stds(vec![0.0])
+            .build(Sequential);
+        assert!(matches!(invalid, Err(InferenceError::InvalidParameters(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip_reproduces_predictions() {
+        let model = LogisticRegressionBuilder::new(vec![0.5f32, -1.5, 2.0], 0.25)
+            .feature_means(vec![1.0, -2.0, 0.5])
+            .build(Sequential)
+            .unwrap();
+        let json = serde_json::to_string(&model).unwrap();
+        let loaded: LogisticRegression<f32, Sequential> = serde_json::from_str(&json).unwrap();
+
+        let input = [3.0, 1.0, -2.0];
+        assert_eq!(loaded.predict(&input), model.predict(&input));
+        assert_eq!(loaded.feature_means(), model.feature_means());
     }
 }
@@ -0,0 +1,190 @@
+// File: src/models/logistic/categorical.rs
+//
+// Logistic regression over a mix of dense numeric inputs and high-cardinality
+// categorical inputs given as indices. A categorical input contributes the
+// single weight `weights[offset + index]` instead of a multiply, which is what
+// a one-hot encoding would compute without materializing the sparse vector.
+//
+// The weights follow the input order: a numeric input owns one weight and a
+// categorical input with C categories owns the next C weights. Indices are
+// stored as f32, so they are exact up to 2^24.
+
+use crate::models::error::InferenceError;
+use crate::models::logistic::activation::sigmoid;
+
+/// How one input position contributes to the score
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureKind {
+    /// Multiplied by its weight
+    Numeric,
+    /// Index into a table of `cardinality` weights
+    Categorical { cardinality: usize },
+}
+
+/// Kinds of every input position and where each one's weights start
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureSpec {
+    kinds: Vec<FeatureKind>,
+    offsets: Vec<usize>,
+    num_weights: usize,
+}
+
+impl FeatureSpec {
+    pub fn new(kinds: Vec<FeatureKind>) -> Self {
+        let mut offsets = Vec::with_capacity(kinds.len());
+        let mut num_weights = 0;
+        for kind in &kinds {
+            offsets.push(num_weights);
+            num_weights += match kind {
+                FeatureKind::Numeric => 1,
+                FeatureKind::Categorical { cardinality } => *cardinality,
+            };
+        }
+        Self {
+            kinds,
+            offsets,
+            num_weights,
+        }
+    }
+
+    pub fn kinds(&self) -> &[FeatureKind] {
+        &self.kinds
+    }
+
+    /// Index of the first weight owned by input `position`
+    pub fn offset(&self, position: usize) -> usize {
+        self.offsets[position]
+    }
+
+    /// Number of values in one input sample
+    pub fn num_inputs(&self) -> usize {
+        self.kinds.len()
+    }
+
+    /// Length of the weight vector the spec lays out
+    pub fn num_weights(&self) -> usize {
+        self.num_weights
+    }
+}
+
+pub struct CategoricalLogisticRegression {
+    weights: Vec<f32>,
+    bias: f32,
+    spec: FeatureSpec,
+}
+
+impl CategoricalLogisticRegression {
+    pub fn new(weights: Vec<f32>, bias: f32, spec: FeatureSpec) -> Result<Self, InferenceError> {
+        if weights.len() != spec.num_weights() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: spec.num_weights(),
+                got: weights.len(),
+            });
+        }
+        Ok(Self {
+            weights,
+            bias,
+            spec,
+        })
+    }
+
+    /// Number of values in one input sample, not the number of weights
+    pub fn num_features(&self) -> usize {
+        self.spec.num_inputs()
+    }
+
+    pub fn spec(&self) -> &FeatureSpec {
+        &self.spec
+    }
+
+    pub fn predict(&self, input: &[f32]) -> f32 {
+        self.try_predict(input).unwrap()
+    }
+
+    pub fn try_predict(&self, input: &[f32]) -> Result<f32, InferenceError> {
+        Ok(sigmoid(self.try_logit(input)?))
+    }
+
+    pub fn logit(&self, input: &[f32]) -> f32 {
+        self.try_logit(input).unwrap()
+    }
+
+    pub fn try_logit(&self, input: &[f32]) -> Result<f32, InferenceError> {
+        if input.len() != self.spec.num_inputs() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: self.spec.num_inputs(),
+                got: input.len(),
+            });
+        }
+        let mut logit = self.bias;
+        for (position, (&value, kind)) in input.iter().zip(&self.spec.kinds).enumerate() {
+            let offset = self.spec.offsets[position];
+            logit += match *kind {
+                FeatureKind::Numeric => self.weights[offset] * value,
+                FeatureKind::Categorical { cardinality } => {
+                    // also rejects NaN, which fails every comparison
+                    if !(value >= 0.0 && value < cardinality as f32 && value.fract() == 0.0) {
+                        return Err(InferenceError::CategoryOutOfRange {
+                            position,
+                            cardinality,
+                        });
+                    }
+                    self.weights[offset + value as usize]
+                }
+            };
+        }
+        Ok(logit)
+    }
+
+    pub fn predict_batch(&self, data: &[f32], num_features: usize) -> Vec<f32> {
+        assert_eq!(
+            num_features,
+            self.spec.num_inputs(),
+            "Sample width does not match the model"
+        );
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.chunks(num_features)
+            .map(|sample| self.predict(sample))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::logistic::base::{LogisticRegression, Sequential};
+
+    #[test]
+    fn lookup_matches_one_hot_encoding() {
+        let spec = FeatureSpec::new(vec![
+            FeatureKind::Numeric,
+            FeatureKind::Categorical { cardinality: 4 },
+            FeatureKind::Numeric,
+        ]);
+        assert_eq!((spec.num_inputs(), spec.num_weights()), (3, 6));
+        assert_eq!(spec.offset(2), 5);
+
+        let weights = vec![0.5, -1.0, 0.25, 2.0, -0.5, 1.5];
+        let model = CategoricalLogisticRegression::new(weights.clone(), 0.1, spec).unwrap();
+        let dense = LogisticRegression::new(weights, 0.1, Sequential);
+
+        let one_hot = [2.0, 0.0, 0.0, 1.0, 0.0, -1.0];
+        assert!((model.predict(&[2.0, 2.0, -1.0]) - dense.predict(&one_hot)).abs() < 1e-6);
+
+        let out_of_range = Err(InferenceError::CategoryOutOfRange {
+            position: 1,
+            cardinality: 4,
+        });
+        assert_eq!(model.try_predict(&[2.0, 4.0, -1.0]), out_of_range);
+        assert_eq!(model.try_predict(&[2.0, 1.5, -1.0]), out_of_range);
+        assert!(model.try_predict(&[2.0, 1.0]).is_err());
+
+        let partial = std::panic::catch_unwind(|| model.predict_batch(&[2.0, 2.0, -1.0, 2.0], 3));
+        let message = *partial.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("whole number of samples"), "{}", message);
+    }
+}
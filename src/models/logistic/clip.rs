@@ -0,0 +1,135 @@
+// File: src/models/logistic/clip.rs
+//
+// Strategy wrapper that guards against outlier inputs. Features can be clamped
+// before the dot product and the logit before the sigmoid, which keeps scores
+// on garbage inputs finite and away from exactly 0 or 1. With neither range
+// set every call is passed straight to the wrapped strategy.
+
+use crate::models::logistic::activation::sigmoid;
+use crate::models::logistic::base::OptimizationStrategy;
+use crate::models::strategy::ScoreStrategy;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Clip<T> {
+    pub inner: T,
+    /// Inclusive range every feature is clamped to
+    pub input: Option<(f32, f32)>,
+    /// Inclusive range the logit is clamped to. The exact sigmoid is applied to
+    /// the clamped logit, whatever activation the wrapped strategy uses.
+    pub logit: Option<(f32, f32)>,
+}
+
+impl<T> Clip<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            input: None,
+            logit: None,
+        }
+    }
+
+    pub fn input_range(mut self, min: f32, max: f32) -> Self {
+        assert!(min <= max, "Clip range minimum exceeds maximum");
+        self.input = Some((min, max));
+        self
+    }
+
+    pub fn logit_range(mut self, min: f32, max: f32) -> Self {
+        assert!(min <= max, "Clip range minimum exceeds maximum");
+        self.logit = Some((min, max));
+        self
+    }
+}
+
+fn clamp_all(values: &[f32], (min, max): (f32, f32)) -> Vec<f32> {
+    values.iter().map(|x| x.clamp(min, max)).collect()
+}
+
+impl<T: ScoreStrategy> ScoreStrategy for Clip<T> {
+    fn score(&self, weights: &[f32], input: &[f32], bias: f32) -> f32 {
+        let logit = match self.input {
+            Some(range) => self.inner.score(weights, &clamp_all(input, range), bias),
+            None => self.inner.score(weights, input, bias),
+        };
+        match self.logit {
+            Some((min, max)) => logit.clamp(min, max),
+            None => logit,
+        }
+    }
+
+    fn score_batch(
+        &self,
+        weights: &[f32],
+        data: &[f32],
+        bias: f32,
+        num_features: usize,
+    ) -> Vec<f32> {
+        let logits = match self.input {
+            Some(range) => {
+                self.inner
+                    .score_batch(weights, &clamp_all(data, range), bias, num_features)
+            }
+            None => self.inner.score_batch(weights, data, bias, num_features),
+        };
+        match self.logit {
+            Some(range) => clamp_all(&logits, range),
+            None => logits,
+        }
+    }
+}
+
+impl<T: OptimizationStrategy> OptimizationStrategy for Clip<T> {
+    fn activate(&self, logit: f32) -> f32 {
+        match self.logit {
+            Some(_) => sigmoid(logit),
+            None => self.inner.activate(logit),
+        }
+    }
+
+    fn forward_batch(
+        &self,
+        weights: &[f32],
+        data: &[f32],
+        bias: f32,
+        num_features: usize,
+    ) -> Vec<f32> {
+        match (self.input, self.logit) {
+            (_, Some(_)) => self
+                .score_batch(weights, data, bias, num_features)
+                .into_iter()
+                .map(sigmoid)
+                .collect(),
+            // Clamp the whole block once so the inner strategy keeps its batch path
+            (Some(range), None) => {
+                self.inner
+                    .forward_batch(weights, &clamp_all(data, range), bias, num_features)
+            }
+            (None, None) => self.inner.forward_batch(weights, data, bias, num_features),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::logistic::base::{LogisticRegression, Sequential};
+
+    #[test]
+    fn clamps_inputs_and_logit() {
+        let weights = vec![1.0, 1.0];
+        let input = [1000.0, -2.0];
+
+        let plain = LogisticRegression::new(weights.clone(), 0.0, Clip::new(Sequential));
+        assert_eq!(plain.predict(&input), 1.0);
+
+        // features clamped to [-3, 3] give a logit of 1
+        let inputs = Clip::new(Sequential).input_range(-3.0, 3.0);
+        let model = LogisticRegression::new(weights.clone(), 0.0, inputs);
+        assert!((model.predict(&input) - sigmoid(1.0)).abs() < 1e-6);
+
+        let logits = Clip::new(Sequential).logit_range(-10.0, 10.0);
+        let model = LogisticRegression::new(weights, 0.0, logits);
+        assert!((model.predict(&input) - sigmoid(10.0)).abs() < 1e-6);
+        assert_eq!(model.predict_batch(&input, 2), vec![model.predict(&input)]);
+    }
+}
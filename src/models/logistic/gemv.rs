@@ -0,0 +1,36 @@
+// File: src/models/logistic/gemv.rs
+//
+// Logistic regression as a matrix-vector product over the whole batch: the
+// shared `Gemv` scorer from models/strategy.rs followed by the exact sigmoid.
+// Single samples fall back to the sequential dot product, so this strategy
+// only pays off through `predict_batch`. Requires the `gemv` feature.
+
+use crate::models::float::Float;
+use crate::models::logistic::base::OptimizationStrategy;
+pub use crate::models::strategy::Gemv;
+
+impl<F: Float> OptimizationStrategy<F> for Gemv {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::logistic::base::{LogisticRegression, Sequential};
+
+    #[test]
+    fn batch_matches_sequential_predict() {
+        let num_features = 13;
+        let weights: Vec<f32> = (0..num_features).map(|i| (i as f32 * 0.37).sin()).collect();
+        // 11 samples, so the last 3 go through the remainder path
+        let data: Vec<f32> = (0..11 * num_features)
+            .map(|i| (i as f32 * 0.11).cos())
+            .collect();
+
+        let gemv = LogisticRegression::new(weights.clone(), 0.25, Gemv);
+        let sequential = LogisticRegression::new(weights, 0.25, Sequential);
+        let expected: Vec<f32> = data
+            .chunks(num_features)
+            .map(|row| sequential.predict(row))
+            .collect();
+        assert_eq!(gemv.predict_batch(&data, num_features), expected);
+    }
+}
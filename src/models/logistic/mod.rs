@@ -1,5 +1,20 @@
+pub mod activation;
+pub mod auto;
 pub mod base;
+pub mod categorical;
+pub mod clip;
+#[cfg(feature = "gemv")]
+pub mod gemv;
+#[cfg(feature = "rayon")]
+pub mod multicore;
+pub mod quantized;
 #[cfg(target_arch = "aarch64")]
 pub mod simd_arm;
+#[cfg(feature = "portable_simd")]
+pub mod simd_portable;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+pub mod simd_wasm;
 #[cfg(target_arch = "x86_64")]
 pub mod simd_x86;
+pub mod softmax;
+pub mod sparse;
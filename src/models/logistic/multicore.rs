@@ -0,0 +1,157 @@
+// File: src/models/logistic/multicore.rs
+//
+// This file implements the multi-threaded strategy. The per-sample `score` has
+// nothing to parallelize, so `MultiCore` works at the batch level:
+// `score_batch` and `forward_batch` split the block into contiguous shards of
+// whole samples and run the wrapped strategy's batch call on each shard inside
+// a rayon thread pool. Single samples go straight to the wrapped strategy.
+// Requires the `rayon` feature.
+
+use crate::models::float::Float;
+use crate::models::logistic::base::OptimizationStrategy;
+use crate::models::strategy::ScoreStrategy;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+use std::sync::Arc;
+
+// Smallest shard worth handing to another thread
+const DEFAULT_MIN_SAMPLES_PER_TASK: usize = 256;
+
+pub struct MultiCore<T> {
+    pub inner: T,
+    // None runs on rayon's global pool
+    pool: Option<Arc<ThreadPool>>,
+    min_samples_per_task: usize,
+}
+
+impl<T> MultiCore<T> {
+    /// Shards batches across rayon's global thread pool
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            pool: None,
+            min_samples_per_task: DEFAULT_MIN_SAMPLES_PER_TASK,
+        }
+    }
+
+    /// Shards batches across a dedicated pool of `num_threads` threads
+    pub fn with_threads(inner: T, num_threads: usize) -> Result<Self, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new().num_threads(num_threads).build()?;
+        Ok(Self::with_pool(inner, Arc::new(pool)))
+    }
+
+    /// Shards batches across an existing pool, which may be shared with other models
+    pub fn with_pool(inner: T, pool: Arc<ThreadPool>) -> Self {
+        Self {
+            pool: Some(pool),
+            ..Self::new(inner)
+        }
+    }
+
+    /// Sets the smallest number of samples given to one task, so small batches
+    /// are not split into shards too short to repay the scheduling
+    pub fn min_samples_per_task(mut self, samples: usize) -> Self {
+        assert!(samples > 0, "A task needs at least one sample");
+        self.min_samples_per_task = samples;
+        self
+    }
+
+    fn num_threads(&self) -> usize {
+        match &self.pool {
+            Some(pool) => pool.current_num_threads(),
+            None => rayon::current_num_threads(),
+        }
+    }
+}
+
+impl<T: Sync> MultiCore<T> {
+    // Runs `batch` on shards of whole samples, or on the whole block when it is
+    // too small to split
+    fn sharded<F: Copy + Send + Sync>(
+        &self,
+        data: &[F],
+        num_features: usize,
+        batch: impl Fn(&[F]) -> Vec<F> + Sync,
+    ) -> Vec<F> {
+        let num_samples = data.len() / num_features;
+        let samples_per_task = num_samples
+            .div_ceil(self.num_threads())
+            .max(self.min_samples_per_task);
+        if samples_per_task >= num_samples {
+            return batch(data);
+        }
+
+        let shard = || -> Vec<F> {
+            data.par_chunks(samples_per_task * num_features)
+                .map(&batch)
+                .collect::<Vec<_>>()
+                .concat()
+        };
+        match &self.pool {
+            Some(pool) => pool.install(shard),
+            None => shard(),
+        }
+    }
+}
+
+impl<F, T> ScoreStrategy<F> for MultiCore<T>
+where
+    F: Float + Send + Sync,
+    T: ScoreStrategy<F> + Sync,
+{
+    fn score(&self, weights: &[F], input: &[F], bias: F) -> F {
+        self.inner.score(weights, input, bias)
+    }
+
+    fn score_batch(&self, weights: &[F], data: &[F], bias: F, num_features: usize) -> Vec<F> {
+        self.sharded(data, num_features, |block| {
+            self.inner.score_batch(weights, block, bias, num_features)
+        })
+    }
+}
+
+impl<F, T> OptimizationStrategy<F> for MultiCore<T>
+where
+    F: Float + Send + Sync,
+    T: OptimizationStrategy<F> + Sync,
+{
+    fn activate(&self, logit: F) -> F {
+        self.inner.activate(logit)
+    }
+
+    fn forward_batch(&self, weights: &[F], data: &[F], bias: F, num_features: usize) -> Vec<F> {
+        self.sharded(data, num_features, |block| {
+            self.inner.forward_batch(weights, block, bias, num_features)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::logistic::base::{LogisticRegression, Sequential};
+
+    #[test]
+    fn sharded_batch_matches_sequential() {
+        let num_features = 7;
+        let weights: Vec<f32> = (0..num_features).map(|i| (i as f32 * 0.41).sin()).collect();
+        // 23 samples over 3 threads gives shards of 8, so the last one is short
+        let data: Vec<f32> = (0..23 * num_features)
+            .map(|i| (i as f32 * 0.13).cos())
+            .collect();
+
+        let strategy = MultiCore::with_threads(Sequential, 3)
+            .unwrap()
+            .min_samples_per_task(5);
+        let multicore = LogisticRegression::new(weights.clone(), -0.5, strategy);
+        let sequential = LogisticRegression::new(weights, -0.5, Sequential);
+        assert_eq!(
+            multicore.predict_batch(&data, num_features),
+            sequential.predict_batch(&data, num_features)
+        );
+        assert_eq!(
+            multicore.predict(&data[..7]),
+            sequential.predict(&data[..7])
+        );
+    }
+}
@@ -0,0 +1,195 @@
+// File: src/models/logistic/quantized.rs
+//
+// This file implements logistic regression over int8 quantized weights using
+// per-tensor affine quantization: w = scale * (q - zero_point).
+//
+// OptimizationStrategy works on f32 weight slices, so quantized weights live in
+// their own model type rather than behind a strategy.
+//
+// The weights stay quantized in memory and the dot product is rescaled once
+// per sample instead of dequantizing every weight:
+//
+//     w . x = scale * (sum_i q_i * x_i - zero_point * sum_i x_i)
+//
+// Parameter file layout (little-endian):
+//
+//     [scale: f32, zero_point: f32, bias: f32, q_0: i8 .. q_{n-1}: i8]
+//
+// The zero point is stored as f32 to keep the header in the same encoding as
+// every other parameter file, and must be an integer in the i8 range.
+
+use crate::models::error::InferenceError;
+use crate::models::logistic::activation::sigmoid;
+use crate::utils::io::open_input;
+use std::io::{self, Read};
+use std::path::Path;
+
+// Size in bytes of the f32 header preceding the quantized weights
+const HEADER_BYTES: usize = 12;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantizedLogisticRegression {
+    weights: Vec<i8>,
+    scale: f32,
+    zero_point: i8,
+    bias: f32,
+}
+
+impl QuantizedLogisticRegression {
+    pub fn new(weights: Vec<i8>, scale: f32, zero_point: i8, bias: f32) -> Self {
+        Self {
+            weights,
+            scale,
+            zero_point,
+            bias,
+        }
+    }
+
+    /// Quantizes f32 weights onto the full i8 range with an asymmetric affine mapping
+    pub fn quantize(weights: &[f32], bias: f32) -> Self {
+        let min = weights.iter().copied().fold(0.0f32, f32::min);
+        let max = weights.iter().copied().fold(0.0f32, f32::max);
+        let scale = if max > min { (max - min) / 255.0 } else { 1.0 };
+        let zero_point = (-128.0 - min / scale).round().clamp(-128.0, 127.0) as i8;
+        let quantized = weights
+            .iter()
+            .map(|&w| (w / scale + zero_point as f32).round().clamp(-128.0, 127.0) as i8)
+            .collect();
+        Self::new(quantized, scale, zero_point, bias)
+    }
+
+    /// Loads quantized parameters using the layout documented above
+    pub fn from_param_file(path: &Path) -> io::Result<Self> {
+        let mut file = open_input(path)?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        if bytes.len() <= HEADER_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "quantized parameters need a {} byte header and at least one weight, found {} bytes",
+                    HEADER_BYTES,
+                    bytes.len()
+                ),
+            ));
+        }
+        let header: Vec<f32> = bytes[..HEADER_BYTES]
+            .chunks(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        let (scale, zero_point, bias) = (header[0], header[1], header[2]);
+        if zero_point.fract() != 0.0 || !(-128.0..=127.0).contains(&zero_point) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "zero point must be an integer in the i8 range, found {}",
+                    zero_point
+                ),
+            ));
+        }
+        let weights = bytes[HEADER_BYTES..].iter().map(|&b| b as i8).collect();
+        Ok(Self::new(weights, scale, zero_point as i8, bias))
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn bias(&self) -> f32 {
+        self.bias
+    }
+
+    /// Expands the weights back to f32
+    pub fn dequantize(&self) -> Vec<f32> {
+        self.weights
+            .iter()
+            .map(|&q| self.scale * (q as f32 - self.zero_point as f32))
+            .collect()
+    }
+
+    pub fn predict(&self, input: &[f32]) -> f32 {
+        self.try_predict(input).unwrap()
+    }
+
+    pub fn try_predict(&self, input: &[f32]) -> Result<f32, InferenceError> {
+        if self.weights.len() != input.len() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: self.weights.len(),
+                got: input.len(),
+            });
+        }
+        let mut dot = 0.0f32;
+        let mut input_sum = 0.0f32;
+        for (&q, &x) in self.weights.iter().zip(input.iter()) {
+            dot += q as f32 * x;
+            input_sum += x;
+        }
+        let logit = self.scale * (dot - self.zero_point as f32 * input_sum) + self.bias;
+        Ok(sigmoid(logit))
+    }
+
+    pub fn predict_batch(&self, data: &[f32], num_features: usize) -> Vec<f32> {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.chunks(num_features)
+            .map(|chunk| self.predict(chunk))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::logistic::base::{LogisticRegression, Sequential};
+    use crate::models::strategy::ScoreStrategy;
+
+    #[test]
+    fn quantized_labels_match_f32_reference() {
+        let dim = 64;
+        let weights: Vec<f32> = (0..dim).map(|i| (i as f32 * 0.73).sin() * 0.8).collect();
+        let bias = 0.05;
+        let reference = LogisticRegression::new(weights.clone(), bias, Sequential);
+        let quantized = QuantizedLogisticRegression::quantize(&weights, bias);
+
+        // worst case rounding error is half a quantization step per weight
+        let mut disagreements = 0;
+        for s in 0..500 {
+            let input: Vec<f32> = (0..dim)
+                .map(|i| ((s * dim + i) as f32 * 0.31).cos())
+                .collect();
+            let logit_bound = 0.5 * quantized.scale * input.iter().map(|x| x.abs()).sum::<f32>();
+
+            let p_ref = reference.predict(&input);
+            let p_q = quantized.predict(&input);
+            let logit_ref = Sequential.score(&weights, &input, bias);
+            if (p_ref > 0.5) != (p_q > 0.5) {
+                disagreements += 1;
+                assert!(
+                    logit_ref.abs() <= logit_bound,
+                    "label flipped for a sample outside the quantization error bound"
+                );
+            }
+            assert!((p_ref - p_q).abs() < 0.05, "{} vs {}", p_ref, p_q);
+        }
+        assert!(disagreements < 10);
+    }
+
+    #[test]
+    fn parses_param_bytes() {
+        let mut bytes = Vec::new();
+        for v in [0.5f32, 2.0, -1.0] {
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+        bytes.extend([4i8 as u8, -2i8 as u8]);
+        let model = QuantizedLogisticRegression::from_bytes(&bytes).unwrap();
+        assert_eq!(model.dequantize(), vec![1.0, -2.0]);
+        assert_eq!(model.bias(), -1.0);
+    }
+}
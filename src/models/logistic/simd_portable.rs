@@ -0,0 +1,11 @@
+// File: src/models/logistic/simd_portable.rs
+//
+// Portable std::simd strategy for logistic regression, for targets such as
+// wasm32 that have no hand-written kernels: the shared scorer from
+// models/strategy.rs followed by the exact sigmoid. Requires the
+// `portable_simd` feature and a nightly toolchain.
+
+use crate::models::logistic::base::OptimizationStrategy;
+pub use crate::models::strategy::Portable;
+
+impl OptimizationStrategy for Portable {}
@@ -0,0 +1,10 @@
+// File: src/models/logistic/simd_wasm.rs
+//
+// WebAssembly SIMD128 strategy for logistic regression: the shared scorer from
+// models/strategy.rs followed by the exact sigmoid. The module is only
+// compiled for wasm32 builds with the simd128 target feature enabled.
+
+use crate::models::logistic::base::OptimizationStrategy;
+pub use crate::models::strategy::SIMD128;
+
+impl OptimizationStrategy for SIMD128 {}
@@ -1,131 +1,134 @@
-// File: src/models/logistic/simd.rs
+// File: src/models/logistic/simd_x86.rs
 //
-// This file implements SIMD-optimized logistic regression using SSE and AVX instructions.
-// The optimizations focus on vectorized dot products and efficient sigmoid calculations.
-//
-// Project structure:
-// src/
-//   models/
-//     logistic/
-//       mod.rs        - Module exports
-//       base.rs       - Core trait definitions
-//       simd.rs       - This file - SIMD optimizations
-//       multicore.rs  - Multi-threading optimizations
-
-//
-// SIMD Register Types:
-// - SSE: 128-bit registers that can process 4 x 32-bit floats in parallel
-// - AVX: 256-bit registers that can process 8 x 32-bit floats in parallel
-//
-// The implementations below focus on single-core SIMD optimizations.
-// Multi-threading optimizations are handled separately in multicore.rs.
+// x86 strategies for logistic regression. The SSE through AVX-512 scorers are
+// the shared ones from models/strategy.rs and keep the exact sigmoid; only
+// `AVXBatch`, which vectorizes the activation itself, is specific to this model.
 
+use crate::models::kernels::dot_avx;
+use crate::models::logistic::activation::{fast_sigmoid, fast_sigmoid_avx};
 use crate::models::logistic::base::OptimizationStrategy;
+use crate::models::strategy::ScoreStrategy;
+pub use crate::models::strategy::{AVX, AVX2, AVX512, SSE, SSE41};
 use std::arch::x86_64::*;
 
-// SSE optimized implementation using 128-bit registers
-#[cfg(target_arch = "x86_64")]
-pub struct SSE;
-
-#[cfg(target_arch = "x86_64")]
-impl OptimizationStrategy for SSE {
-    fn forward(&self, weights: &[f32], input: &[f32], bias: f32) -> f32 {
-        unsafe {
-            let feature_dim = weights.len();
-            let mut sum_vec = _mm_setzero_ps();
-            let mut i = 0;
-
-            // Check 16-byte alignment for optimal SSE performance
-            let input_aligned = (input.as_ptr() as usize) % 16 == 0;
-            let weights_aligned = (weights.as_ptr() as usize) % 16 == 0;
-
-            // Process 4 elements at a time using SSE
-            if input_aligned && weights_aligned {
-                while i + 4 <= feature_dim {
-                    let x_vec = _mm_load_ps(&input[i]);
-                    let w_vec = _mm_load_ps(&weights[i]);
-                    sum_vec = _mm_add_ps(sum_vec, _mm_mul_ps(x_vec, w_vec));
-                    i += 4;
-                }
-            } else {
-                while i + 4 <= feature_dim {
-                    let x_vec = _mm_loadu_ps(&input[i]);
-                    let w_vec = _mm_loadu_ps(&weights[i]);
-                    sum_vec = _mm_add_ps(sum_vec, _mm_mul_ps(x_vec, w_vec));
-                    i += 4;
-                }
-            }
+impl OptimizationStrategy for SSE {}
 
-            // Handle remaining elements sequentially
-            let mut sum_scalar = 0.0;
-            while i < feature_dim {
-                sum_scalar += input[i] * weights[i];
-                i += 1;
-            }
+impl OptimizationStrategy for SSE41 {}
+
+impl OptimizationStrategy for AVX {}
+
+impl OptimizationStrategy for AVX2 {}
 
-            // Combine SSE vector sum with scalar sum
-            let mut sum_array: [f32; 4] = [0.0; 4];
-            _mm_storeu_ps(sum_array.as_mut_ptr(), sum_vec);
-            let sum = sum_array.iter().sum::<f32>() + sum_scalar + bias;
+impl OptimizationStrategy for AVX512 {}
 
-            sigmoid(sum)
+// Batch AVX implementation that activates 8 samples at a time. The logits come
+// from the AVX dot product per sample, then the bias add, `fast_sigmoid` and the
+// optional threshold run on all 8 lanes together, which amortizes the
+// activation that dominates small feature dimensions. Single samples and the
+// last few of a batch take the same steps one lane at a time.
+#[derive(Default)]
+pub struct AVXBatch {
+    threshold: Option<f32>,
+}
+
+impl AVXBatch {
+    /// Returns 1.0 for probabilities above `threshold` and 0.0 otherwise instead
+    /// of the probabilities themselves
+    pub fn with_threshold(threshold: f32) -> Self {
+        Self {
+            threshold: Some(threshold),
         }
     }
 }
 
-// AVX optimized implementation using 256-bit registers
-#[cfg(target_arch = "x86_64")]
-pub struct AVX;
-
-#[cfg(target_arch = "x86_64")]
-impl OptimizationStrategy for AVX {
-    fn forward(&self, weights: &[f32], input: &[f32], bias: f32) -> f32 {
-        unsafe {
-            let feature_dim = weights.len();
-            let mut sum_vec = _mm256_setzero_ps();
-            let mut i = 0;
-
-            // Check 32-byte alignment for optimal AVX performance
-            let input_aligned = (input.as_ptr() as usize) % 32 == 0;
-            let weights_aligned = (weights.as_ptr() as usize) % 32 == 0;
-
-            // Process 8 elements at a time using AVX
-            if input_aligned && weights_aligned {
-                while i + 8 <= feature_dim {
-                    let x_vec = _mm256_load_ps(&input[i]);
-                    let w_vec = _mm256_load_ps(&weights[i]);
-                    let mul = _mm256_mul_ps(x_vec, w_vec);
-                    sum_vec = _mm256_add_ps(sum_vec, mul);
-                    i += 8;
-                }
-            } else {
-                while i + 8 <= feature_dim {
-                    let x_vec = _mm256_loadu_ps(&input[i]);
-                    let w_vec = _mm256_loadu_ps(&weights[i]);
-                    let mul = _mm256_mul_ps(x_vec, w_vec);
-                    sum_vec = _mm256_add_ps(sum_vec, mul);
-                    i += 8;
-                }
-            }
+impl ScoreStrategy for AVXBatch {
+    fn score(&self, weights: &[f32], input: &[f32], bias: f32) -> f32 {
+        dot_avx(weights, input) + bias
+    }
+}
 
-            // Handle remaining elements sequentially
-            let mut sum_scalar = 0.0;
-            while i < feature_dim {
-                sum_scalar += input[i] * weights[i];
-                i += 1;
-            }
+impl OptimizationStrategy for AVXBatch {
+    fn activate(&self, logit: f32) -> f32 {
+        let prob = fast_sigmoid(logit);
+        match self.threshold {
+            Some(threshold) => f32::from(u8::from(prob > threshold)),
+            None => prob,
+        }
+    }
 
-            // Combine AVX vector sum with scalar sum
-            let mut sum_array: [f32; 8] = [0.0; 8];
-            _mm256_storeu_ps(sum_array.as_mut_ptr(), sum_vec);
-            let sum = sum_array.iter().sum::<f32>() + sum_scalar + bias;
+    fn forward_batch(
+        &self,
+        weights: &[f32],
+        data: &[f32],
+        bias: f32,
+        num_features: usize,
+    ) -> Vec<f32> {
+        let mut out = Vec::with_capacity(data.len() / num_features);
+        // Rows left over after the groups of 8, or every row when the CPU lacks AVX
+        let rest = if is_x86_feature_detected!("avx") {
+            let mut groups = data.chunks_exact(8 * num_features);
+            for group in groups.by_ref() {
+                let mut logits = [0.0f32; 8];
+                for (logit, row) in logits.iter_mut().zip(group.chunks(num_features)) {
+                    *logit = dot_avx(weights, row);
+                }
+                out.extend_from_slice(&unsafe { self.activate_avx(logits, bias) });
+            }
+            groups.remainder()
+        } else {
+            data
+        };
+        out.extend(
+            rest.chunks(num_features)
+                .map(|row| self.forward(weights, row, bias)),
+        );
+        out
+    }
+}
 
-            sigmoid(sum)
+impl AVXBatch {
+    #[target_feature(enable = "avx")]
+    unsafe fn activate_avx(&self, logits: [f32; 8], bias: f32) -> [f32; 8] {
+        let logits = _mm256_add_ps(_mm256_loadu_ps(logits.as_ptr()), _mm256_set1_ps(bias));
+        let mut probs = fast_sigmoid_avx(logits);
+        if let Some(threshold) = self.threshold {
+            let above = _mm256_cmp_ps::<_CMP_GT_OQ>(probs, _mm256_set1_ps(threshold));
+            probs = _mm256_and_ps(above, _mm256_set1_ps(1.0));
         }
+        let mut out = [0.0f32; 8];
+        _mm256_storeu_ps(out.as_mut_ptr(), probs);
+        out
     }
 }
-// sigmoid will be replaced later with a polynomial approximation
-#[inline]
-fn sigmoid(x: f32) -> f32 {
-    1.0 / (1.0 + (-x).exp())
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::logistic::activation::FastSigmoid;
+    use crate::models::logistic::base::LogisticRegression;
+
+    #[test]
+    fn batch_lanes_match_per_sample_fast_sigmoid() {
+        let num_features = 5;
+        let weights: Vec<f32> = (0..num_features)
+            .map(|i| (i as f32 * 0.9).sin() * 3.0)
+            .collect();
+        // 19 samples: two full groups of 8 and a remainder of 3
+        let data: Vec<f32> = (0..19 * num_features)
+            .map(|i| (i as f32 * 0.23).cos())
+            .collect();
+
+        let reference = LogisticRegression::new(weights.clone(), 0.3, FastSigmoid(AVX));
+        let expected = reference.predict_batch(&data, num_features);
+        let batch = LogisticRegression::new(weights.clone(), 0.3, AVXBatch::default());
+        assert_eq!(batch.predict_batch(&data, num_features), expected);
+
+        let thresholded = LogisticRegression::new(weights, 0.3, AVXBatch::with_threshold(0.5));
+        let labels: Vec<f32> = expected
+            .iter()
+            .map(|&p| if p > 0.5 { 1.0 } else { 0.0 })
+            .collect();
+        assert_eq!(thresholded.predict_batch(&data, num_features), labels);
+        assert!(labels.contains(&0.0) && labels.contains(&1.0));
+    }
 }
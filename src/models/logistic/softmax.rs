@@ -0,0 +1,127 @@
+// File: src/models/logistic/softmax.rs
+//
+// This file implements multinomial (softmax) logistic regression. Each class
+// has its own weight row and bias, and the per-class linear scores are computed
+// with the same ScoreStrategy used by the binary linear models so any SIMD
+// strategy applies unchanged.
+
+use crate::models::error::InferenceError;
+use crate::models::float::Float;
+use crate::models::strategy::ScoreStrategy;
+
+// Multi-class logistic regression with a K x features weight matrix
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoftmaxRegression<F: Float, T: ScoreStrategy<F>> {
+    weights: Vec<Vec<F>>,
+    biases: Vec<F>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    strategy: T,
+}
+
+impl<F: Float, T: ScoreStrategy<F>> SoftmaxRegression<F, T> {
+    pub fn new(weights: Vec<Vec<F>>, biases: Vec<F>, strategy: T) -> Self {
+        assert!(!weights.is_empty(), "At least one class is required");
+        assert_eq!(
+            weights.len(),
+            biases.len(),
+            "One bias per class is required"
+        );
+        assert!(
+            weights.iter().all(|row| row.len() == weights[0].len()),
+            "All weight rows must have the same dimension"
+        );
+        Self {
+            weights,
+            biases,
+            strategy,
+        }
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.weights[0].len()
+    }
+
+    /// Returns the index of the most probable class
+    pub fn predict(&self, input: &[F]) -> usize {
+        self.try_predict(input).unwrap()
+    }
+
+    pub fn try_predict(&self, input: &[F]) -> Result<usize, InferenceError> {
+        // softmax is monotonic so the argmax of the logits is the argmax of the probabilities
+        let logits = self.logits(input)?;
+        Ok(argmax(&logits))
+    }
+
+    /// Returns the softmax distribution over all classes
+    pub fn predict_proba(&self, input: &[F]) -> Vec<F> {
+        self.try_predict_proba(input).unwrap()
+    }
+
+    pub fn try_predict_proba(&self, input: &[F]) -> Result<Vec<F>, InferenceError> {
+        let mut logits = self.logits(input)?;
+        softmax(&mut logits);
+        Ok(logits)
+    }
+
+    fn logits(&self, input: &[F]) -> Result<Vec<F>, InferenceError> {
+        if self.num_features() != input.len() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: self.num_features(),
+                got: input.len(),
+            });
+        }
+        Ok(self
+            .weights
+            .iter()
+            .zip(self.biases.iter())
+            .map(|(row, &bias)| self.strategy.score(row, input, bias))
+            .collect())
+    }
+}
+
+// In-place softmax, shifting by the max logit so exp never overflows
+pub(crate) fn softmax<F: Float>(logits: &mut [F]) {
+    let max = logits[argmax(logits)];
+    let mut total = F::ZERO;
+    for logit in logits.iter_mut() {
+        *logit = (*logit - max).exp();
+        total = total + *logit;
+    }
+    for p in logits.iter_mut() {
+        *p = *p / total;
+    }
+}
+
+pub(crate) fn argmax<F: Float>(values: &[F]) -> usize {
+    let mut best = 0;
+    for (i, &v) in values.iter().enumerate().skip(1) {
+        if v > values[best] {
+            best = i;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::logistic::base::Sequential;
+
+    #[test]
+    fn softmax_is_stable_for_large_logits() {
+        let model = SoftmaxRegression::new(
+            vec![vec![1000.0f32, 0.0], vec![0.0, 1000.0], vec![0.0, 0.0]],
+            vec![0.0, 0.0, 0.0],
+            Sequential,
+        );
+        let proba = model.predict_proba(&[1.0, 0.5]);
+        assert!(proba.iter().all(|p| p.is_finite()));
+        assert!((proba.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+        assert_eq!(model.predict(&[1.0, 0.5]), 0);
+        assert_eq!(model.predict(&[0.5, 1.0]), 1);
+    }
+}
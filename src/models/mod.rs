@@ -1,2 +1,11 @@
+pub mod aligned;
+pub mod ensemble;
+pub mod error;
+pub mod float;
+pub mod kernels;
+pub mod knn;
 pub mod logistic;
+pub mod naive_bayes;
+pub mod predictor;
+pub mod strategy;
 pub mod svm;
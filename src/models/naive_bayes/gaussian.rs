@@ -0,0 +1,230 @@
+// File: src/models/naive_bayes/gaussian.rs
+//
+// This file implements Gaussian Naive Bayes. Each class c has a prior and a
+// per-feature mean and variance, and the predicted class maximizes
+//
+//     log p(c) - 1/2 * sum_i ln(var_ci) - 1/2 * sum_i (x_i - mean_ci)^2 / var_ci
+//
+// The -d/2 * ln(2 pi) term is the same for every class and is dropped. The
+// per-class constant and inverse variances are computed once at construction,
+// so scoring a class is a single weighted squared distance.
+//
+// Parameter file layout (flat little-endian f32, same encoding as the other models):
+//
+//     [num_classes, num_features,
+//      prior_0 .. prior_{k-1},
+//      mean_0[0] .. mean_{k-1}[d-1],
+//      var_0[0] .. var_{k-1}[d-1]]
+//
+// The two counts are stored as f32 and must be exact non-negative integers.
+
+use crate::models::error::{header_count, InferenceError};
+use crate::models::float::Float;
+use crate::models::kernels::weighted_squared_distance_scalar;
+use crate::models::logistic::softmax::{argmax, softmax};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+// Number of header values preceding the priors
+const HEADER_LEN: usize = 2;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GaussianNaiveBayes<F: Float> {
+    means: Vec<Vec<F>>,
+    inverse_variances: Vec<Vec<F>>,
+    // log prior minus half the summed log variances, per class
+    log_constants: Vec<F>,
+}
+
+impl<F: Float> GaussianNaiveBayes<F> {
+    pub fn new(means: Vec<Vec<F>>, variances: Vec<Vec<F>>, priors: Vec<F>) -> Self {
+        assert!(!means.is_empty(), "At least one class is required");
+        assert!(
+            means.len() == variances.len() && means.len() == priors.len(),
+            "Every class needs means, variances and a prior"
+        );
+        assert!(
+            means
+                .iter()
+                .chain(variances.iter())
+                .all(|row| row.len() == means[0].len()),
+            "All mean and variance rows must have the same dimension"
+        );
+        assert!(
+            variances.iter().flatten().all(|&var| var > F::ZERO),
+            "Variances must be positive"
+        );
+        assert!(
+            priors.iter().all(|&prior| prior > F::ZERO),
+            "Class priors must be positive"
+        );
+
+        let half = F::ONE / (F::ONE + F::ONE);
+        let log_constants = variances
+            .iter()
+            .zip(priors.iter())
+            .map(|(row, &prior)| prior.ln() - half * row.iter().map(|&var| var.ln()).sum::<F>())
+            .collect();
+        let inverse_variances = variances
+            .iter()
+            .map(|row| row.iter().map(|&var| F::ONE / var).collect())
+            .collect();
+        Self {
+            means,
+            inverse_variances,
+            log_constants,
+        }
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.means.len()
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.means[0].len()
+    }
+
+    /// Returns the index of the most probable class
+    pub fn predict(&self, input: &[F]) -> usize {
+        self.try_predict(input).unwrap()
+    }
+
+    pub fn try_predict(&self, input: &[F]) -> Result<usize, InferenceError> {
+        Ok(argmax(&self.joint_log_likelihood(input)?))
+    }
+
+    /// Returns the posterior distribution over all classes
+    pub fn predict_proba(&self, input: &[F]) -> Vec<F> {
+        self.try_predict_proba(input).unwrap()
+    }
+
+    pub fn try_predict_proba(&self, input: &[F]) -> Result<Vec<F>, InferenceError> {
+        let mut scores = self.joint_log_likelihood(input)?;
+        softmax(&mut scores);
+        Ok(scores)
+    }
+
+    /// Unnormalized log posterior of every class, up to a constant shared by all classes
+    pub fn joint_log_likelihood(&self, input: &[F]) -> Result<Vec<F>, InferenceError> {
+        if self.num_features() != input.len() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: self.num_features(),
+                got: input.len(),
+            });
+        }
+        let half = F::ONE / (F::ONE + F::ONE);
+        Ok(self
+            .means
+            .iter()
+            .zip(self.inverse_variances.iter())
+            .zip(self.log_constants.iter())
+            .map(|((mean, inverse_variance), &constant)| {
+                constant - half * weighted_squared_distance_scalar(input, mean, inverse_variance)
+            })
+            .collect())
+    }
+
+    pub fn predict_batch(&self, data: &[F], num_features: usize) -> Vec<usize> {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.chunks(num_features)
+            .map(|chunk| self.predict(chunk))
+            .collect()
+    }
+
+    /// Same as `predict_batch` but spreads samples across the rayon thread pool
+    #[cfg(feature = "rayon")]
+    pub fn predict_batch_parallel(&self, data: &[F], num_features: usize) -> Vec<usize>
+    where
+        F: Send + Sync,
+    {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.par_chunks(num_features)
+            .map(|chunk| self.predict(chunk))
+            .collect()
+    }
+}
+
+impl GaussianNaiveBayes<f32> {
+    /// Builds a model from a flat parameter buffer using the layout documented above
+    pub fn from_params(params: &[f32]) -> Result<Self, InferenceError> {
+        if params.len() < HEADER_LEN {
+            return Err(InferenceError::InvalidParameters(format!(
+                "expected a {} value header, found {} values",
+                HEADER_LEN,
+                params.len()
+            )));
+        }
+        let num_classes = header_count(params[0], "number of classes")?;
+        let num_features = header_count(params[1], "number of features")?;
+
+        let table = num_classes * num_features;
+        let expected = HEADER_LEN + num_classes + 2 * table;
+        if num_classes == 0 || num_features == 0 || params.len() != expected {
+            return Err(InferenceError::InvalidParameters(format!(
+                "{} classes of dimension {} need {} values, found {}",
+                num_classes,
+                num_features,
+                expected,
+                params.len()
+            )));
+        }
+
+        let priors = params[HEADER_LEN..HEADER_LEN + num_classes].to_vec();
+        let means_start = HEADER_LEN + num_classes;
+        let variances = &params[means_start + table..];
+        if !variances.iter().all(|&var| var > 0.0) {
+            return Err(InferenceError::InvalidParameters(
+                "variances must be positive".to_string(),
+            ));
+        }
+        if !priors.iter().all(|&prior| prior > 0.0) {
+            return Err(InferenceError::InvalidParameters(
+                "class priors must be positive".to_string(),
+            ));
+        }
+
+        let rows = |values: &[f32]| {
+            values
+                .chunks(num_features)
+                .map(|row| row.to_vec())
+                .collect()
+        };
+        Ok(Self::new(
+            rows(&params[means_start..means_start + table]),
+            rows(variances),
+            priors,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gaussian_posterior_from_params() {
+        // two classes in 2D centred on (0, 0) and (4, 4)
+        let params = [2.0, 2.0, 0.5, 0.5, 0.0, 0.0, 4.0, 4.0, 1.0, 1.0, 1.0, 1.0];
+        let model = GaussianNaiveBayes::from_params(&params).unwrap();
+        assert_eq!(model.num_classes(), 2);
+        assert_eq!(model.predict(&[0.5, -0.5]), 0);
+        assert_eq!(model.predict(&[3.0, 5.0]), 1);
+
+        // equal priors and variances, so the midpoint is a coin flip
+        let proba = model.predict_proba(&[2.0, 2.0]);
+        assert!((proba[0] - 0.5).abs() < 1e-6 && (proba[1] - 0.5).abs() < 1e-6);
+
+        let mut zero_variance = params;
+        zero_variance[8] = 0.0;
+        assert!(GaussianNaiveBayes::from_params(&zero_variance).is_err());
+        assert!(GaussianNaiveBayes::from_params(&params[..11]).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+// File: src/models/predictor.rs
+//
+// Object-safe interface shared by the binary f32 models, so generic code (the
+// ensemble, a future server) can hold "some model" as a `Box<dyn Predictor>`.
+// The generic model structs stay the primary API; this trait only erases the
+// model type and its strategy.
+
+use crate::models::error::InferenceError;
+use crate::models::logistic::base::{LogisticRegression, OptimizationStrategy as LogisticStrategy};
+use crate::models::logistic::quantized::QuantizedLogisticRegression;
+use crate::models::strategy::ScoreStrategy;
+use crate::models::svm::base::SupportVectorMachine;
+
+pub trait Predictor: Send + Sync {
+    fn num_features(&self) -> usize;
+
+    /// Probability of the positive class for logistic models, decision value for SVMs
+    fn try_predict_score(&self, input: &[f32]) -> Result<f32, InferenceError>;
+
+    /// The model's own label: 0/1 for logistic models, -1/1 for SVMs (or 0 on a
+    /// tie with `TieBreak::Zero`). Positive labels are always greater than zero.
+    fn try_predict_label(&self, input: &[f32]) -> Result<i64, InferenceError>;
+
+    fn predict_score(&self, input: &[f32]) -> f32 {
+        self.try_predict_score(input).unwrap()
+    }
+
+    fn predict_label(&self, input: &[f32]) -> i64 {
+        self.try_predict_label(input).unwrap()
+    }
+}
+
+impl<T: LogisticStrategy + Send + Sync> Predictor for LogisticRegression<f32, T> {
+    fn num_features(&self) -> usize {
+        self.num_features()
+    }
+
+    fn try_predict_score(&self, input: &[f32]) -> Result<f32, InferenceError> {
+        self.try_predict(input)
+    }
+
+    fn try_predict_label(&self, input: &[f32]) -> Result<i64, InferenceError> {
+        Ok(i64::from(self.try_predict(input)? > 0.5))
+    }
+}
+
+impl Predictor for QuantizedLogisticRegression {
+    fn num_features(&self) -> usize {
+        self.num_features()
+    }
+
+    fn try_predict_score(&self, input: &[f32]) -> Result<f32, InferenceError> {
+        self.try_predict(input)
+    }
+
+    fn try_predict_label(&self, input: &[f32]) -> Result<i64, InferenceError> {
+        Ok(i64::from(self.try_predict(input)? > 0.5))
+    }
+}
+
+impl<T: ScoreStrategy + Send + Sync> Predictor for SupportVectorMachine<f32, T> {
+    fn num_features(&self) -> usize {
+        self.num_features()
+    }
+
+    fn try_predict_score(&self, input: &[f32]) -> Result<f32, InferenceError> {
+        self.decision_function(input)
+    }
+
+    fn try_predict_label(&self, input: &[f32]) -> Result<i64, InferenceError> {
+        Ok(i64::from(self.try_predict(input)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::logistic::base::Sequential;
+    use crate::models::svm::base::Sequential as SvmSequential;
+
+    #[test]
+    fn models_behind_trait_objects() {
+        let models: Vec<Box<dyn Predictor>> = vec![
+            Box::new(LogisticRegression::new(vec![1.0, -1.0], 0.0, Sequential)),
+            Box::new(SupportVectorMachine::new(
+                vec![1.0, -1.0],
+                0.0,
+                SvmSequential,
+            )),
+        ];
+        let labels: Vec<i64> = models
+            .iter()
+            .map(|m| m.predict_label(&[2.0, 1.0]))
+            .collect();
+        assert_eq!(labels, vec![1, 1]);
+        let labels: Vec<i64> = models
+            .iter()
+            .map(|m| m.predict_label(&[1.0, 2.0]))
+            .collect();
+        assert_eq!(labels, vec![0, -1]);
+        assert!((models[1].predict_score(&[3.0, 1.0]) - 2.0).abs() < 1e-6);
+        assert!(models[0].try_predict_score(&[1.0]).is_err());
+    }
+}
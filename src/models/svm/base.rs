@@ -2,6 +2,8 @@
 //
 // This file implements the core SVM functionality with a flexible
 // optimization strategy pattern, similar to the logistic regression implementation.
+// The strategies are the shared scorers from models/strategy.rs; the SVM takes
+// the sign of their score rather than applying an activation.
 
 //use std::arch::x86_64::*;
 //use std::sync::Arc;
@@ -13,43 +15,292 @@
 //#[cfg(target_arch = "aarch64")]
 //use std::arch::aarch64 as arch;
 
-// Trait for different optimization strategies
-pub trait OptimizationStrategy {
-    fn forward(&self, weights: &[f32], input: &[f32], bias: f32) -> f32;
+use crate::models::aligned::AlignedVec;
+use crate::models::error::InferenceError;
+use crate::models::float::Float;
+use crate::models::strategy::ScoreStrategy;
+pub use crate::models::strategy::Sequential;
+use crate::utils::io::{read_params, BiasPosition};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::io;
+use std::path::Path;
+
+/// Label for a decision value of exactly zero. Exact zeros are rare with real
+/// data but common with integer-valued inputs and weights. A NaN score is not
+/// a tie and is always labelled -1.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    /// -1, the historical behaviour of `score > 0`
+    #[default]
+    Negative,
+    /// 1
+    Positive,
+    /// 0, marking the sample as undecided
+    Zero,
 }
 
-// Basic sequential implementation
-pub struct Sequential;
-impl OptimizationStrategy for Sequential {
-    fn forward(&self, weights: &[f32], input: &[f32], bias: f32) -> f32 {
-        let dot_product: f32 = weights.iter().zip(input.iter()).map(|(w, x)| w * x).sum();
-        dot_product + bias
+impl TieBreak {
+    /// Label for `score` under this rule
+    pub fn label<F: Float>(self, score: F) -> i32 {
+        if score > F::ZERO {
+            1
+        } else if score == F::ZERO {
+            match self {
+                TieBreak::Negative => -1,
+                TieBreak::Positive => 1,
+                TieBreak::Zero => 0,
+            }
+        } else {
+            -1
+        }
     }
 }
 
-// Main SVM struct that can use different optimization strategies
-pub struct SupportVectorMachine<T: OptimizationStrategy> {
-    weights: Vec<f32>,
-    bias: f32,
+// Main SVM struct that can use different optimization strategies.
+// As with LogisticRegression, serde skips the strategy and rebuilds it from `Default`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SupportVectorMachine<F: Float, T: ScoreStrategy<F>> {
+    // Cache-line aligned so SIMD loads of the weights never straddle two lines
+    weights: AlignedVec<F>,
+    bias: F,
+    #[cfg_attr(feature = "serde", serde(skip))]
     strategy: T,
+    // Platt scaling (A, B) mapping decision values to probabilities
+    #[cfg_attr(feature = "serde", serde(default))]
+    platt_params: Option<(F, F)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    tie_break: TieBreak,
 }
 
-impl<T: OptimizationStrategy> SupportVectorMachine<T> {
-    pub fn new(weights: Vec<f32>, bias: f32, strategy: T) -> Self {
+impl<F: Float, T: ScoreStrategy<F>> SupportVectorMachine<F, T> {
+    pub fn new(weights: Vec<F>, bias: F, strategy: T) -> Self {
         Self {
-            weights,
+            weights: weights.into(),
             bias,
             strategy,
+            platt_params: None,
+            tie_break: TieBreak::default(),
         }
     }
 
-    pub fn predict(&self, input: &[f32]) -> i32 {
-        assert_eq!(self.weights.len(), input.len(), "Input dimension mismatch");
-        let score = self.strategy.forward(&self.weights, input, self.bias);
-        if score > 0.0 {
-            1
-        } else {
-            -1
+    /// Model without an intercept, such as scikit-learn's `fit_intercept=False`
+    pub fn new_no_bias(weights: Vec<F>, strategy: T) -> Self {
+        Self::new(weights, F::ZERO, strategy)
+    }
+
+    /// Zero-pads the weights to the next multiple of `lanes`, see
+    /// `LogisticRegression::pad_to_lanes`
+    pub fn pad_to_lanes(mut self, lanes: usize) -> Self {
+        let width = self.weights.len().next_multiple_of(lanes.max(1));
+        let mut weights = self.weights.to_vec();
+        weights.resize(width, F::ZERO);
+        self.weights = weights.into();
+        self
+    }
+
+    /// Sets the Platt scaling parameters fitted on this model's decision values
+    pub fn with_platt_params(mut self, a: F, b: F) -> Self {
+        self.platt_params = Some((a, b));
+        self
+    }
+
+    /// Sets the label `predict` returns for a decision value of exactly zero
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+
+    pub fn platt_params(&self) -> Option<(F, F)> {
+        self.platt_params
+    }
+
+    /// Maps a decision value to a probability with the Platt scaling parameters,
+    /// None when the model has none
+    pub fn calibrate(&self, score: F) -> Option<F> {
+        let (a, b) = self.platt_params?;
+        Some(F::ONE / (F::ONE + (a * score + b).exp()))
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn weights(&self) -> &[F] {
+        &self.weights
+    }
+
+    pub fn bias(&self) -> F {
+        self.bias
+    }
+
+    pub fn predict(&self, input: &[F]) -> i32 {
+        self.try_predict(input).unwrap()
+    }
+
+    pub fn try_predict(&self, input: &[F]) -> Result<i32, InferenceError> {
+        Ok(self.tie_break.label(self.decision_function(input)?))
+    }
+
+    /// Raw decision value `w . x + b` before taking the sign. Its magnitude grows
+    /// with the distance from the hyperplane, so it can rank samples by confidence.
+    pub fn decision_function(&self, input: &[F]) -> Result<F, InferenceError> {
+        if self.weights.len() != input.len() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: self.weights.len(),
+                got: input.len(),
+            });
+        }
+        Ok(self.strategy.score(&self.weights, input, self.bias))
+    }
+
+    /// Calibrated probability of the positive class, `1 / (1 + exp(A * score + B))`.
+    /// None when the model has no Platt scaling parameters.
+    pub fn predict_proba(&self, input: &[F]) -> Option<F> {
+        self.try_predict_proba(input).unwrap()
+    }
+
+    pub fn try_predict_proba(&self, input: &[F]) -> Result<Option<F>, InferenceError> {
+        if self.platt_params.is_none() {
+            return Ok(None);
         }
+        Ok(self.calibrate(self.decision_function(input)?))
+    }
+
+    /// Calibrated probabilities for every sample, see `predict_proba`
+    pub fn predict_proba_batch(&self, data: &[F], num_features: usize) -> Option<Vec<F>> {
+        self.platt_params?;
+        self.decision_function_batch(data, num_features)
+            .into_iter()
+            .map(|score| self.calibrate(score))
+            .collect()
+    }
+
+    /// Raw decision values for every sample, see `decision_function`
+    pub fn decision_function_batch(&self, data: &[F], num_features: usize) -> Vec<F> {
+        assert_eq!(
+            num_features,
+            self.weights.len(),
+            "Sample width does not match the model"
+        );
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        self.strategy
+            .score_batch(&self.weights, data, self.bias, num_features)
+    }
+
+    pub fn predict_batch(&self, data: &[F], num_features: usize) -> Vec<i32> {
+        self.decision_function_batch(data, num_features)
+            .into_iter()
+            .map(|score| self.tie_break.label(score))
+            .collect()
+    }
+
+    /// Same as `predict_batch` but spreads samples across the rayon thread pool.
+    /// The strategy is shared between threads so it must be `Sync`.
+    #[cfg(feature = "rayon")]
+    pub fn predict_batch_parallel(&self, data: &[F], num_features: usize) -> Vec<i32>
+    where
+        F: Send + Sync,
+        T: Sync,
+    {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.par_chunks(num_features)
+            .map(|chunk| self.predict(chunk))
+            .collect()
+    }
+
+    /// Same as `decision_function_batch` but spreads samples across the rayon thread pool
+    #[cfg(feature = "rayon")]
+    pub fn decision_function_batch_parallel(&self, data: &[F], num_features: usize) -> Vec<F>
+    where
+        F: Send + Sync,
+        T: Sync,
+    {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.par_chunks(num_features)
+            .map(|chunk| self.decision_function(chunk).unwrap())
+            .collect()
+    }
+}
+
+impl<T: ScoreStrategy> SupportVectorMachine<f32, T> {
+    /// Loads a model from a raw little-endian f32 file holding the weights and
+    /// the bias at `bias_position`
+    pub fn from_param_file(
+        path: &Path,
+        bias_position: BiasPosition,
+        strategy: T,
+    ) -> io::Result<Self> {
+        let (weights, bias) = read_params(path, bias_position)?;
+        Ok(Self::new(weights, bias, strategy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predict_is_sign_of_decision_function() {
+        let model = SupportVectorMachine::new(vec![2.0f32, -1.0], 0.5, Sequential);
+        let data = [1.0, 0.0, 0.0, 3.0, 0.25, 1.0];
+
+        let scores = model.decision_function_batch(&data, 2);
+        assert_eq!(scores, vec![2.5, -2.5, 0.0]);
+        // a score of exactly zero falls on the negative side unless configured
+        assert_eq!(model.predict_batch(&data, 2), vec![1, -1, -1]);
+        let model = model.with_tie_break(TieBreak::Zero);
+        assert_eq!(model.predict_batch(&data, 2), vec![1, -1, 0]);
+        let model = model.with_tie_break(TieBreak::Positive);
+        assert_eq!(model.predict(&[0.25, 1.0]), 1);
+        assert_eq!(TieBreak::Zero.label(f32::NAN), -1);
+        assert_eq!(TieBreak::Positive.label(-0.0f32), 1);
+        assert_eq!(
+            model.decision_function(&[1.0]),
+            Err(InferenceError::DimensionMismatch {
+                expected: 2,
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn new_no_bias_scores_without_an_intercept() {
+        let model = SupportVectorMachine::new_no_bias(vec![2.0f32, -1.0], Sequential);
+        assert_eq!(model.bias(), 0.0);
+        assert_eq!(model.decision_function(&[1.0, 0.0]), Ok(2.0));
+        assert_eq!(model.predict(&[1.0, 2.0]), -1);
+    }
+
+    #[test]
+    fn platt_scaling_calibrates_decision_values() {
+        let model = SupportVectorMachine::new(vec![2.0f32, -1.0], 0.5, Sequential);
+        assert_eq!(model.predict_proba(&[1.0, 0.0]), None);
+
+        // A < 0, so larger decision values are more likely positive
+        let model = model.with_platt_params(-1.5, 0.25);
+        let data = [1.0, 0.0, 0.0, 3.0];
+        let probs = model.predict_proba_batch(&data, 2).unwrap();
+        let expected = 1.0 / (1.0 + (-1.5f32 * 2.5 + 0.25).exp());
+        assert!((probs[0] - expected).abs() < 1e-6);
+        assert!(probs[0] > 0.5 && probs[1] < 0.5);
+        assert_eq!(model.predict_proba(&[1.0, 0.0]), Some(probs[0]));
     }
 }
@@ -0,0 +1,175 @@
+// File: src/models/svm/kernel.rs
+//
+// This file implements a kernel SVM using the radial basis function (RBF) kernel.
+// The decision function is
+//
+//     f(x) = sum_i alpha_i * exp(-gamma * ||x - sv_i||^2) + b
+//
+// where alpha_i are the dual coefficients (already multiplied by the labels).
+//
+// Parameter file layout (flat little-endian f32, same encoding as the linear models):
+//
+//     [num_support_vectors, num_features, gamma, intercept,
+//      alpha_0 .. alpha_{n-1},
+//      sv_0[0] .. sv_0[d-1], sv_1[0] .. sv_{n-1}[d-1]]
+//
+// The two counts are stored as f32 and must be exact non-negative integers.
+
+use crate::models::error::{header_count, InferenceError};
+use crate::models::float::Float;
+use crate::models::kernels::squared_distance_scalar;
+
+// Number of header values preceding the dual coefficients
+const HEADER_LEN: usize = 4;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KernelSvm<F: Float> {
+    support_vectors: Vec<Vec<F>>,
+    dual_coefs: Vec<F>,
+    intercept: F,
+    gamma: F,
+}
+
+impl<F: Float> KernelSvm<F> {
+    pub fn new(support_vectors: Vec<Vec<F>>, dual_coefs: Vec<F>, intercept: F, gamma: F) -> Self {
+        assert!(
+            !support_vectors.is_empty(),
+            "At least one support vector is required"
+        );
+        assert_eq!(
+            support_vectors.len(),
+            dual_coefs.len(),
+            "One dual coefficient per support vector is required"
+        );
+        assert!(
+            support_vectors
+                .iter()
+                .all(|sv| sv.len() == support_vectors[0].len()),
+            "All support vectors must have the same dimension"
+        );
+        Self {
+            support_vectors,
+            dual_coefs,
+            intercept,
+            gamma,
+        }
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.support_vectors[0].len()
+    }
+
+    pub fn num_support_vectors(&self) -> usize {
+        self.support_vectors.len()
+    }
+
+    /// Raw decision value before taking the sign
+    pub fn decision_function(&self, input: &[F]) -> Result<F, InferenceError> {
+        if self.num_features() != input.len() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: self.num_features(),
+                got: input.len(),
+            });
+        }
+        let score = self
+            .support_vectors
+            .iter()
+            .zip(self.dual_coefs.iter())
+            .map(|(sv, &alpha)| alpha * (-self.gamma * squared_distance_scalar(sv, input)).exp())
+            .sum::<F>();
+        Ok(score + self.intercept)
+    }
+
+    pub fn predict(&self, input: &[F]) -> i32 {
+        self.try_predict(input).unwrap()
+    }
+
+    pub fn try_predict(&self, input: &[F]) -> Result<i32, InferenceError> {
+        let score = self.decision_function(input)?;
+        Ok(if score > F::ZERO { 1 } else { -1 })
+    }
+
+    pub fn predict_batch(&self, data: &[F], num_features: usize) -> Vec<i32> {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.chunks(num_features)
+            .map(|chunk| self.predict(chunk))
+            .collect()
+    }
+}
+
+impl KernelSvm<f32> {
+    /// Builds a model from a flat parameter buffer using the layout documented above
+    pub fn from_params(params: &[f32]) -> Result<Self, InferenceError> {
+        if params.len() < HEADER_LEN {
+            return Err(InferenceError::InvalidParameters(format!(
+                "expected a {} value header, found {} values",
+                HEADER_LEN,
+                params.len()
+            )));
+        }
+        let num_sv = header_count(params[0], "number of support vectors")?;
+        let num_features = header_count(params[1], "number of features")?;
+        let gamma = params[2];
+        let intercept = params[3];
+
+        let expected = HEADER_LEN + num_sv + num_sv * num_features;
+        if num_sv == 0 || num_features == 0 || params.len() != expected {
+            return Err(InferenceError::InvalidParameters(format!(
+                "{} support vectors of dimension {} need {} values, found {}",
+                num_sv,
+                num_features,
+                expected,
+                params.len()
+            )));
+        }
+
+        let dual_coefs = params[HEADER_LEN..HEADER_LEN + num_sv].to_vec();
+        let support_vectors = params[HEADER_LEN + num_sv..]
+            .chunks(num_features)
+            .map(|sv| sv.to_vec())
+            .collect();
+        Ok(Self::new(support_vectors, dual_coefs, intercept, gamma))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rbf_decision_from_params() {
+        // two support vectors in 2D with opposite labels
+        let params = [2.0, 2.0, 0.5, 0.1, 1.0, -1.0, 1.0, 1.0, -1.0, -1.0];
+        let model = KernelSvm::from_params(&params).unwrap();
+        assert_eq!(model.num_support_vectors(), 2);
+        assert_eq!(model.num_features(), 2);
+
+        let expected = (-0.5f32 * 0.0).exp() - (-0.5f32 * 8.0).exp() + 0.1;
+        let score = model.decision_function(&[1.0, 1.0]).unwrap();
+        assert!((score - expected).abs() < 1e-6);
+        assert_eq!(model.predict(&[0.9, 1.2]), 1);
+        assert_eq!(model.predict(&[-0.9, -1.2]), -1);
+
+        assert!(KernelSvm::from_params(&params[..9]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip_reproduces_decisions() {
+        let params = [2.0, 2.0, 0.5, 0.1, 1.0, -1.0, 1.0, 1.0, -1.0, -1.0];
+        let model = KernelSvm::from_params(&params).unwrap();
+        let json = serde_json::to_string(&model).unwrap();
+        let loaded: KernelSvm<f32> = serde_json::from_str(&json).unwrap();
+
+        for input in [[1.0, 1.0], [0.9, 1.2], [-0.9, -1.2]] {
+            assert_eq!(
+                loaded.decision_function(&input),
+                model.decision_function(&input)
+            );
+        }
+    }
+}
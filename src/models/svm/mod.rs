@@ -1 +1,3 @@
 pub mod base;
+pub mod kernel;
+pub mod multi_output;
@@ -0,0 +1,184 @@
+// File: src/models/svm/multi_output.rs
+//
+// This file implements a multi-label linear SVM made of independent one-vs-rest
+// classifiers, the layout scikit-learn's LinearSVC and OneVsRestClassifier
+// export. Each label has its own weight row and bias and gets its own sign, so a
+// sample can carry any number of labels. The per-label decision values use the
+// same ScoreStrategy as the single-output SVM.
+//
+// Parameter file layout for K labels (flat little-endian f32, same encoding as
+// the other models):
+//
+//     [num_labels, num_features,
+//      w_0[0] .. w_0[d-1], w_1[0] .. w_{K-1}[d-1],
+//      b_0 .. b_{K-1}]
+//
+// which is `coef_` (K x d, row-major) followed by `intercept_`. The two counts
+// are stored as f32 and must be exact non-negative integers.
+
+use crate::models::error::{header_count, InferenceError};
+use crate::models::float::Float;
+use crate::models::strategy::ScoreStrategy;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+// Number of header values preceding the weight matrix
+const HEADER_LEN: usize = 2;
+
+// One-vs-rest SVM with a K x features weight matrix and K biases
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiOutputSvm<F: Float, T: ScoreStrategy<F>> {
+    weights: Vec<Vec<F>>,
+    biases: Vec<F>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    strategy: T,
+}
+
+impl<F: Float, T: ScoreStrategy<F>> MultiOutputSvm<F, T> {
+    pub fn new(weights: Vec<Vec<F>>, biases: Vec<F>, strategy: T) -> Self {
+        assert!(!weights.is_empty(), "At least one label is required");
+        assert_eq!(
+            weights.len(),
+            biases.len(),
+            "One bias per label is required"
+        );
+        assert!(
+            weights.iter().all(|row| row.len() == weights[0].len()),
+            "All weight rows must have the same dimension"
+        );
+        Self {
+            weights,
+            biases,
+            strategy,
+        }
+    }
+
+    pub fn num_labels(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.weights[0].len()
+    }
+
+    /// Returns 1 or -1 for every label
+    pub fn predict(&self, input: &[F]) -> Vec<i32> {
+        self.try_predict(input).unwrap()
+    }
+
+    pub fn try_predict(&self, input: &[F]) -> Result<Vec<i32>, InferenceError> {
+        Ok(self
+            .decision_function(input)?
+            .into_iter()
+            .map(|score| if score > F::ZERO { 1 } else { -1 })
+            .collect())
+    }
+
+    /// Raw decision value `w_k . x + b_k` of every label
+    pub fn decision_function(&self, input: &[F]) -> Result<Vec<F>, InferenceError> {
+        if self.num_features() != input.len() {
+            return Err(InferenceError::DimensionMismatch {
+                expected: self.num_features(),
+                got: input.len(),
+            });
+        }
+        Ok(self
+            .weights
+            .iter()
+            .zip(self.biases.iter())
+            .map(|(row, &bias)| self.strategy.score(row, input, bias))
+            .collect())
+    }
+
+    pub fn predict_batch(&self, data: &[F], num_features: usize) -> Vec<Vec<i32>> {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.chunks(num_features)
+            .map(|chunk| self.predict(chunk))
+            .collect()
+    }
+
+    /// Same as `predict_batch` but spreads samples across the rayon thread pool
+    #[cfg(feature = "rayon")]
+    pub fn predict_batch_parallel(&self, data: &[F], num_features: usize) -> Vec<Vec<i32>>
+    where
+        F: Send + Sync,
+        T: Sync,
+    {
+        assert_eq!(
+            data.len() % num_features,
+            0,
+            "Data is not a whole number of samples"
+        );
+        data.par_chunks(num_features)
+            .map(|chunk| self.predict(chunk))
+            .collect()
+    }
+}
+
+impl<T: ScoreStrategy> MultiOutputSvm<f32, T> {
+    /// Builds a model from a flat parameter buffer using the layout documented above
+    pub fn from_params(params: &[f32], strategy: T) -> Result<Self, InferenceError> {
+        if params.len() < HEADER_LEN {
+            return Err(InferenceError::InvalidParameters(format!(
+                "expected a {} value header, found {} values",
+                HEADER_LEN,
+                params.len()
+            )));
+        }
+        let num_labels = header_count(params[0], "number of labels")?;
+        let num_features = header_count(params[1], "number of features")?;
+
+        let table = num_labels * num_features;
+        let expected = HEADER_LEN + table + num_labels;
+        if num_labels == 0 || num_features == 0 || params.len() != expected {
+            return Err(InferenceError::InvalidParameters(format!(
+                "{} labels of dimension {} need {} values, found {}",
+                num_labels,
+                num_features,
+                expected,
+                params.len()
+            )));
+        }
+
+        let weights = params[HEADER_LEN..HEADER_LEN + table]
+            .chunks(num_features)
+            .map(|row| row.to_vec())
+            .collect();
+        let biases = params[HEADER_LEN + table..].to_vec();
+        Ok(Self::new(weights, biases, strategy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::svm::base::{Sequential, SupportVectorMachine};
+
+    #[test]
+    fn labels_match_independent_svms() {
+        // three labels in 2D
+        let params = [3.0, 2.0, 1.0, 0.0, 0.0, 1.0, -1.0, -1.0, 0.0, -0.5, 0.25];
+        let model = MultiOutputSvm::from_params(&params, Sequential).unwrap();
+        assert_eq!(model.num_labels(), 3);
+        assert_eq!(model.predict(&[1.0, 1.0]), vec![1, 1, -1]);
+
+        let data = [1.0, 1.0, -2.0, 0.25, 0.5, -3.0];
+        let rows = [[1.0, 0.0], [0.0, 1.0], [-1.0, -1.0]];
+        for (label, (row, bias)) in rows.iter().zip([0.0, -0.5, 0.25]).enumerate() {
+            let single = SupportVectorMachine::new(row.to_vec(), bias, Sequential);
+            let expected = single.predict_batch(&data, 2);
+            let got: Vec<i32> = model
+                .predict_batch(&data, 2)
+                .iter()
+                .map(|labels| labels[label])
+                .collect();
+            assert_eq!(got, expected);
+        }
+
+        assert!(MultiOutputSvm::from_params(&params[..10], Sequential).is_err());
+    }
+}
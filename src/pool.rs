@@ -0,0 +1,72 @@
+// File: src/pool.rs
+//
+// Persistent rayon thread pool for serving loops. Building a pool spawns its
+// worker threads, which costs far more than scoring a typical batch, so a
+// server should build one `InferencePool` at startup and run every batch in it:
+//
+//     let pool = InferencePool::new(4)?;
+//     loop {
+//         let probs = pool.install(|| model.predict_batch_parallel(&batch, num_features));
+//         ...
+//     }
+//
+// Every `*_parallel` method runs on whichever pool it is called from, so the
+// models need no changes. `MultiCore::with_pool` takes `shared()` to shard
+// batches across the same workers. Requires the `rayon` feature.
+
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+use std::sync::Arc;
+
+/// Worker pool built once and reused across batches; clones share the threads
+#[derive(Clone)]
+pub struct InferencePool {
+    pool: Arc<ThreadPool>,
+}
+
+impl InferencePool {
+    /// Spawns `num_threads` workers, or one per CPU when `num_threads` is 0
+    pub fn new(num_threads: usize) -> Result<Self, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new().num_threads(num_threads).build()?;
+        Ok(Self {
+            pool: Arc::new(pool),
+        })
+    }
+
+    pub fn num_threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Runs `f` on this pool, so the rayon calls inside it use these workers
+    pub fn install<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        self.pool.install(f)
+    }
+
+    /// Handle to the underlying pool, e.g. for `MultiCore::with_pool`
+    pub fn shared(&self) -> Arc<ThreadPool> {
+        Arc::clone(&self.pool)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::logistic::base::{LogisticRegression, Sequential};
+    use crate::models::logistic::multicore::MultiCore;
+
+    #[test]
+    fn one_pool_serves_many_batches() {
+        let pool = InferencePool::new(2).unwrap();
+        let model = LogisticRegression::new(vec![0.5f32, -1.0, 0.25], 0.1, Sequential);
+        let data: Vec<f32> = (0..300).map(|i| (i % 7) as f32 - 3.0).collect();
+        let expected = model.predict_batch(&data, 3);
+        for _ in 0..3 {
+            assert_eq!(
+                pool.install(|| model.predict_batch_parallel(&data, 3)),
+                expected
+            );
+        }
+
+        let sharded = model.with_strategy(MultiCore::with_pool(Sequential, pool.shared()));
+        assert_eq!(sharded.predict_batch(&data, 3), expected);
+    }
+}
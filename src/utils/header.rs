@@ -0,0 +1,123 @@
+// File: src/utils/header.rs
+//
+// Optional header that makes a raw f32 parameter file self-describing. Files
+// without it are plain f32 blobs as before, so every exporter keeps working.
+//
+// Layout (12 bytes, always little-endian, followed by the f32 parameters in the
+// file's byte order):
+//
+//     b"FINF"  version: u8  model: u8  reserved: [0u8; 2]  num_features: u32
+//
+// The reserved bytes keep the parameters 4-byte aligned. Read as a little-endian
+// f32 the magic is about 13186.3, which no sane first weight equals exactly.
+
+use crate::error::{Error, Result};
+
+pub const MAGIC: &[u8; 4] = b"FINF";
+
+// Only version written and understood so far
+pub const VERSION: u8 = 1;
+
+pub const HEADER_LEN: usize = 12;
+
+/// Model family declared in the header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelKind {
+    Logistic,
+    Svm,
+    NaiveBayes,
+    Knn,
+}
+
+impl ModelKind {
+    fn to_byte(self) -> u8 {
+        match self {
+            ModelKind::Logistic => 1,
+            ModelKind::Svm => 2,
+            ModelKind::NaiveBayes => 3,
+            ModelKind::Knn => 4,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self> {
+        match byte {
+            1 => Ok(ModelKind::Logistic),
+            2 => Ok(ModelKind::Svm),
+            3 => Ok(ModelKind::NaiveBayes),
+            4 => Ok(ModelKind::Knn),
+            other => Err(invalid(format!("unknown model type {} in header", other))),
+        }
+    }
+}
+
+/// Metadata stored ahead of the parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParamHeader {
+    pub model: ModelKind,
+    pub num_features: u32,
+}
+
+impl ParamHeader {
+    /// Splits a header off the front of `bytes`, returning None and the whole
+    /// buffer when the magic is absent
+    pub fn parse(bytes: &[u8]) -> Result<(Option<Self>, &[u8])> {
+        if !bytes.starts_with(MAGIC) {
+            return Ok((None, bytes));
+        }
+        if bytes.len() < HEADER_LEN {
+            return Err(invalid("truncated parameter header"));
+        }
+        if bytes[4] != VERSION {
+            return Err(invalid(format!(
+                "unsupported parameter header version {}",
+                bytes[4]
+            )));
+        }
+        let header = Self {
+            model: ModelKind::from_byte(bytes[5])?,
+            num_features: u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+        };
+        Ok((Some(header), &bytes[HEADER_LEN..]))
+    }
+
+    pub fn to_bytes(&self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0; HEADER_LEN];
+        bytes[..4].copy_from_slice(MAGIC);
+        bytes[4] = VERSION;
+        bytes[5] = self.model.to_byte();
+        bytes[8..].copy_from_slice(&self.num_features.to_le_bytes());
+        bytes
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> Error {
+    Error::MalformedHeader(msg.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_round_trips_and_is_optional() {
+        let header = ParamHeader {
+            model: ModelKind::Svm,
+            num_features: 784,
+        };
+        let mut bytes = header.to_bytes().to_vec();
+        bytes.extend_from_slice(&1.5f32.to_le_bytes());
+        let (parsed, rest) = ParamHeader::parse(&bytes).unwrap();
+        assert_eq!(parsed, Some(header));
+        assert_eq!(rest, &1.5f32.to_le_bytes());
+
+        let raw = 2.0f32.to_le_bytes();
+        assert_eq!(ParamHeader::parse(&raw).unwrap(), (None, &raw[..]));
+
+        assert!(matches!(
+            ParamHeader::parse(&bytes[..8]),
+            Err(Error::MalformedHeader(_))
+        ));
+        bytes[4] = 9;
+        assert!(ParamHeader::parse(&bytes).is_err());
+    }
+}
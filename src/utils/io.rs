@@ -0,0 +1,484 @@
+// File: src/utils/io.rs
+//
+// Readers for parameter and data files. Every reader returns a flat row-major
+// f32 buffer; callers split it into samples using the feature dimension.
+// A path of "-" reads from stdin, so only one input per run can use it.
+// Files ending in `.gz` are decompressed while they are read, so a truncated
+// archive surfaces as `Error::TruncatedFile` on the decompressed length.
+
+use crate::error::Error;
+use crate::utils::header::ParamHeader;
+use flate2::read::GzDecoder;
+use half::f16;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+/// Opens `path` for buffered reading, or the locked stdin handle when it is "-".
+/// Gzip files are decompressed on the fly.
+pub fn open_input(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    if path == Path::new("-") {
+        Ok(Box::new(io::stdin().lock()))
+    } else if is_gzip(path) {
+        Ok(Box::new(BufReader::new(GzDecoder::new(File::open(path)?))))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+/// True when `path` has a `.gz` extension
+pub fn is_gzip(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gz"))
+}
+
+/// Byte order of raw f32 files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Endian {
+    #[default]
+    Little,
+    /// Files written on big-endian machines such as mainframes
+    Big,
+}
+
+impl Endian {
+    #[inline]
+    pub fn f32_from_bytes(self, bytes: [u8; 4]) -> f32 {
+        match self {
+            Endian::Little => f32::from_le_bytes(bytes),
+            Endian::Big => f32::from_be_bytes(bytes),
+        }
+    }
+
+    #[inline]
+    pub fn f16_from_bytes(self, bytes: [u8; 2]) -> f16 {
+        match self {
+            Endian::Little => f16::from_le_bytes(bytes),
+            Endian::Big => f16::from_be_bytes(bytes),
+        }
+    }
+}
+
+/// Element type of raw binary data files. Values are always upcast to f32.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DType {
+    #[default]
+    F32,
+    /// IEEE half precision, halving the file size
+    F16,
+}
+
+impl DType {
+    /// Bytes per value
+    pub fn size(self) -> usize {
+        match self {
+            DType::F32 => 4,
+            DType::F16 => 2,
+        }
+    }
+
+    // Decodes a whole number of values, upcasting to f32
+    fn decode(self, bytes: &[u8], endian: Endian) -> Vec<f32> {
+        match self {
+            DType::F32 => bytes
+                .chunks_exact(4)
+                .map(|b| endian.f32_from_bytes([b[0], b[1], b[2], b[3]]))
+                .collect(),
+            DType::F16 => bytes
+                .chunks_exact(2)
+                .map(|b| endian.f16_from_bytes([b[0], b[1]]).to_f32())
+                .collect(),
+        }
+    }
+}
+
+/// Reads a raw f32 blob in the given byte order
+pub fn read_f32_binary(path: &Path, endian: Endian) -> io::Result<Vec<f32>> {
+    read_binary(path, DType::F32, endian)
+}
+
+/// Reads a raw blob of `dtype` values in the given byte order, upcast to f32
+pub fn read_binary(path: &Path, dtype: DType, endian: Endian) -> io::Result<Vec<f32>> {
+    let mut file = open_input(path)?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    values(&bytes, dtype, endian)
+}
+
+/// Reads a raw f32 parameter file, splitting off its header when there is one
+pub fn read_f32_binary_with_header(
+    path: &Path,
+    endian: Endian,
+) -> io::Result<(Option<ParamHeader>, Vec<f32>)> {
+    let mut file = open_input(path)?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let (header, payload) = ParamHeader::parse(&bytes)?;
+    Ok((header, values(payload, DType::F32, endian)?))
+}
+
+fn values(bytes: &[u8], dtype: DType, endian: Endian) -> io::Result<Vec<f32>> {
+    if !bytes.chunks_exact(dtype.size()).remainder().is_empty() {
+        return Err(Error::TruncatedFile {
+            len: bytes.len(),
+            record_len: dtype.size(),
+        }
+        .into());
+    }
+    Ok(dtype.decode(bytes, endian))
+}
+
+/// Opens a raw f32 data file for streaming in blocks of at most `chunk_size` samples
+pub fn stream_f32_binary(
+    path: &Path,
+    num_features: usize,
+    chunk_size: usize,
+    endian: Endian,
+) -> io::Result<SampleChunks<Box<dyn BufRead>>> {
+    Ok(SampleChunks::new(open_input(path)?, num_features, chunk_size).endian(endian))
+}
+
+/// Iterator over blocks of raw samples, upcast to f32, keeping at most one
+/// block in memory. A torn trailing sample is reported as an error.
+pub struct SampleChunks<R: Read> {
+    reader: R,
+    num_features: usize,
+    chunk_size: usize,
+    endian: Endian,
+    dtype: DType,
+    bytes: Vec<u8>,
+    // Bytes read before the current block
+    offset: usize,
+    done: bool,
+}
+
+impl<R: Read> SampleChunks<R> {
+    /// Streams little-endian f32 samples; see `endian` and `dtype` for other encodings
+    pub fn new(reader: R, num_features: usize, chunk_size: usize) -> Self {
+        assert!(num_features > 0, "Feature dimension must be positive");
+        assert!(chunk_size > 0, "Chunk size must be positive");
+        Self {
+            reader,
+            num_features,
+            chunk_size,
+            endian: Endian::Little,
+            dtype: DType::F32,
+            bytes: vec![0; num_features * chunk_size * DType::F32.size()],
+            offset: 0,
+            done: false,
+        }
+    }
+
+    pub fn endian(mut self, endian: Endian) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    pub fn dtype(mut self, dtype: DType) -> Self {
+        self.dtype = dtype;
+        self.bytes = vec![0; self.num_features * self.chunk_size * dtype.size()];
+        self
+    }
+}
+
+impl<R: Read> Iterator for SampleChunks<R> {
+    type Item = io::Result<Vec<f32>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let filled = match read_full(&mut self.reader, &mut self.bytes) {
+            Ok(filled) => filled,
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        };
+        if filled < self.bytes.len() {
+            self.done = true;
+        }
+        if filled == 0 {
+            return None;
+        }
+
+        let sample_bytes = self.num_features * self.dtype.size();
+        if filled % sample_bytes != 0 {
+            self.done = true;
+            return Some(Err(Error::TruncatedFile {
+                len: self.offset + filled,
+                record_len: sample_bytes,
+            }
+            .into()));
+        }
+
+        self.offset += filled;
+        Some(Ok(self.dtype.decode(&self.bytes[..filled], self.endian)))
+    }
+}
+
+// Fills `buf` as far as possible, returning fewer bytes only at end of input
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Where the bias sits in a flat parameter buffer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BiasPosition {
+    /// Weights followed by the bias
+    #[default]
+    Last,
+    /// Bias followed by the weights
+    First,
+    /// Every value is a weight and the bias is 0
+    None,
+}
+
+/// Reads a raw little-endian parameter file, splitting off the bias at `bias_position`.
+/// A header's feature count must match the weights.
+pub fn read_params(path: &Path, bias_position: BiasPosition) -> io::Result<(Vec<f32>, f32)> {
+    let (weights, bias, _) = read_params_with_header(path, bias_position, Endian::Little)?;
+    Ok((weights, bias))
+}
+
+/// `read_params` for either byte order, also returning the header when there is one
+pub fn read_params_with_header(
+    path: &Path,
+    bias_position: BiasPosition,
+    endian: Endian,
+) -> io::Result<(Vec<f32>, f32, Option<ParamHeader>)> {
+    let (header, params) = read_f32_binary_with_header(path, endian)?;
+    let (weights, bias) = split_bias(params, bias_position)?;
+    if let Some(header) = &header {
+        if header.num_features as usize != weights.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "header declares {} features but the file holds {} weights",
+                    header.num_features,
+                    weights.len()
+                ),
+            ));
+        }
+    }
+    Ok((weights, bias, header))
+}
+
+/// Splits a flat parameter buffer into the weights and the bias
+pub fn split_bias(
+    mut params: Vec<f32>,
+    bias_position: BiasPosition,
+) -> io::Result<(Vec<f32>, f32)> {
+    let min_len = match bias_position {
+        BiasPosition::None => 1,
+        BiasPosition::Last | BiasPosition::First => 2,
+    };
+    if params.len() < min_len {
+        let expected = match bias_position {
+            BiasPosition::None => "at least one weight",
+            BiasPosition::Last | BiasPosition::First => "at least one weight and a bias",
+        };
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "parameters must contain {}, found {} values",
+                expected,
+                params.len()
+            ),
+        ));
+    }
+
+    let bias = match bias_position {
+        BiasPosition::Last => params.pop().unwrap(),
+        BiasPosition::First => params.remove(0),
+        BiasPosition::None => 0.0,
+    };
+    Ok((params, bias))
+}
+
+/// Gathers a column-major (feature-major) matrix into row-major samples. Each
+/// sample's features are read with a stride of the sample count.
+pub fn gather_columns(data: &[f32], num_features: usize) -> Vec<f32> {
+    let num_samples = data.len() / num_features;
+    let mut out = Vec::with_capacity(data.len());
+    for sample in 0..num_samples {
+        out.extend(
+            data[sample..]
+                .iter()
+                .step_by(num_samples)
+                .take(num_features),
+        );
+    }
+    out
+}
+
+/// Reads a CSV file of floats, returning the flattened rows and the column count
+pub fn read_csv(path: &Path) -> io::Result<(Vec<f32>, usize)> {
+    parse_csv(open_input(path)?)
+}
+
+/// Parses CSV rows of floats. Blank lines are skipped and every row must have
+/// the same number of columns as the first one.
+pub fn parse_csv<R: BufRead>(reader: R) -> io::Result<(Vec<f32>, usize)> {
+    let mut values = Vec::new();
+    let mut num_columns = 0;
+
+    for (line_no, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let row_start = values.len();
+        for field in line.split(',') {
+            let value = field.trim().parse::<f32>().map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: invalid value {:?}: {}", line_no + 1, field, e),
+                )
+            })?;
+            values.push(value);
+        }
+
+        let row_len = values.len() - row_start;
+        if row_start == 0 {
+            num_columns = row_len;
+        } else if row_len != num_columns {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "line {}: expected {} columns, found {}",
+                    line_no + 1,
+                    num_columns,
+                    row_len
+                ),
+            ));
+        }
+    }
+
+    Ok((values, num_columns))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_bias_honours_position() {
+        let params = vec![1.0, 2.0, 3.0];
+        assert_eq!(
+            split_bias(params.clone(), BiasPosition::Last).unwrap(),
+            (vec![1.0, 2.0], 3.0)
+        );
+        assert_eq!(
+            split_bias(params.clone(), BiasPosition::First).unwrap(),
+            (vec![2.0, 3.0], 1.0)
+        );
+        assert_eq!(
+            split_bias(params, BiasPosition::None).unwrap(),
+            (vec![1.0, 2.0, 3.0], 0.0)
+        );
+        assert!(split_bias(vec![1.0], BiasPosition::First).is_err());
+        assert!(split_bias(vec![1.0], BiasPosition::None).is_ok());
+        assert!(split_bias(vec![], BiasPosition::None).is_err());
+    }
+
+    #[test]
+    fn gathers_column_major_samples() {
+        // 3 samples of 2 features, stored feature by feature
+        let columns = [1.0, 2.0, 3.0, 10.0, 20.0, 30.0];
+        assert_eq!(
+            gather_columns(&columns, 2),
+            vec![1.0, 10.0, 2.0, 20.0, 3.0, 30.0]
+        );
+        assert_eq!(gather_columns(&columns, 6), columns.to_vec());
+    }
+
+    #[test]
+    fn reads_gzip_files_and_rejects_torn_values() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let write_gz = |name: &str, bytes: &[u8]| {
+            let path = std::env::temp_dir().join(name);
+            let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+            encoder.write_all(bytes).unwrap();
+            encoder.finish().unwrap();
+            path
+        };
+        let values = [1.5f32, -2.0, 0.25];
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+
+        let path = write_gz("fastinference_io_test.bin.gz", &bytes);
+        assert_eq!(read_f32_binary(&path, Endian::Little).unwrap(), values);
+
+        let path = write_gz("fastinference_io_test_torn.bin.gz", &bytes[..10]);
+        let error = Error::from(read_f32_binary(&path, Endian::Little).unwrap_err());
+        assert!(matches!(
+            error,
+            Error::TruncatedFile {
+                len: 10,
+                record_len: 4
+            }
+        ));
+        assert!(!is_gzip(Path::new("data.bin")));
+    }
+
+    #[test]
+    fn csv_rejects_ragged_rows() {
+        let (values, num_columns) = parse_csv("1,2,3\n\n4, 5, 6\n".as_bytes()).unwrap();
+        assert_eq!(values, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(num_columns, 3);
+
+        let err = parse_csv("1,2,3\n4,5\n".as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn sample_chunks_are_bounded_and_reject_torn_samples() {
+        let bytes: Vec<u8> = (0..10).flat_map(|v| (v as f32).to_le_bytes()).collect();
+        let blocks: Vec<Vec<f32>> = SampleChunks::new(bytes.as_slice(), 2, 2)
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[2], vec![8.0, 9.0]);
+
+        let mut torn = SampleChunks::new(&bytes[..bytes.len() - 4], 2, 2);
+        assert!(torn.by_ref().take(2).all(|block| block.is_ok()));
+        assert!(torn.next().unwrap().is_err());
+        assert!(torn.next().is_none());
+
+        let big: Vec<u8> = [1.5f32, -2.0]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect();
+        let mut chunks = SampleChunks::new(big.as_slice(), 2, 1).endian(Endian::Big);
+        assert_eq!(chunks.next().unwrap().unwrap(), vec![1.5, -2.0]);
+
+        // half precision samples take 2 bytes per feature
+        let half: Vec<u8> = [0.5f32, -3.0, 1024.0, 0.25]
+            .iter()
+            .flat_map(|&v| f16::from_f32(v).to_le_bytes())
+            .collect();
+        let blocks: Vec<Vec<f32>> = SampleChunks::new(half.as_slice(), 2, 1)
+            .dtype(DType::F16)
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(blocks, vec![vec![0.5, -3.0], vec![1024.0, 0.25]]);
+        assert!(SampleChunks::new(&half[..7], 2, 2)
+            .dtype(DType::F16)
+            .any(|block| block.is_err()));
+    }
+}
@@ -0,0 +1,125 @@
+// File: src/utils/metrics.rs
+//
+// Evaluation metrics for predictions scored against ground-truth labels. Classes
+// are plain indices, so binary models map their labels to 0 (negative) and
+// 1 (positive) before recording.
+
+use std::fmt;
+
+/// Counts of (actual, predicted) class pairs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfusionMatrix {
+    // counts[actual][predicted]
+    counts: Vec<Vec<usize>>,
+}
+
+impl ConfusionMatrix {
+    pub fn new(num_classes: usize) -> Self {
+        Self {
+            counts: vec![vec![0; num_classes]; num_classes],
+        }
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn record(&mut self, actual: usize, predicted: usize) {
+        self.counts[actual][predicted] += 1;
+    }
+
+    pub fn count(&self, actual: usize, predicted: usize) -> usize {
+        self.counts[actual][predicted]
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// Fraction of samples predicted correctly, NaN before anything is recorded
+    pub fn accuracy(&self) -> f64 {
+        let correct: usize = (0..self.num_classes()).map(|c| self.counts[c][c]).sum();
+        ratio(correct, self.total())
+    }
+
+    /// Fraction of samples predicted as `class` that really are, NaN if none were
+    pub fn precision(&self, class: usize) -> f64 {
+        let predicted: usize = self.counts.iter().map(|row| row[class]).sum();
+        ratio(self.counts[class][class], predicted)
+    }
+
+    /// Fraction of samples of `class` that were predicted as such, NaN if there are none
+    pub fn recall(&self, class: usize) -> f64 {
+        let actual: usize = self.counts[class].iter().sum();
+        ratio(self.counts[class][class], actual)
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    numerator as f64 / denominator as f64
+}
+
+/// Renders accuracy, per-class precision and recall, then the matrix with
+/// actual classes as rows and predicted classes as columns
+impl fmt::Display for ConfusionMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Accuracy: {:.4} ({} samples)",
+            self.accuracy(),
+            self.total()
+        )?;
+        for class in 0..self.num_classes() {
+            writeln!(
+                f,
+                "Class {}: precision {:.4}, recall {:.4}",
+                class,
+                self.precision(class),
+                self.recall(class)
+            )?;
+        }
+
+        let width = self.total().to_string().len().max(6);
+        write!(f, "{:>8}", "actual")?;
+        for class in 0..self.num_classes() {
+            write!(f, " {:>width$}", format!("pred {}", class), width = width)?;
+        }
+        for (actual, row) in self.counts.iter().enumerate() {
+            write!(f, "\n{:>8}", actual)?;
+            for count in row {
+                write!(f, " {:>width$}", count, width = width)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_metrics() {
+        let mut matrix = ConfusionMatrix::new(2);
+        // 3 true positives, 1 false positive, 2 true negatives, 2 false negatives
+        let pairs = [
+            (1, 1),
+            (1, 1),
+            (1, 1),
+            (0, 1),
+            (0, 0),
+            (0, 0),
+            (1, 0),
+            (1, 0),
+        ];
+        for (actual, predicted) in pairs {
+            matrix.record(actual, predicted);
+        }
+        assert_eq!(matrix.total(), 8);
+        assert_eq!(matrix.count(1, 0), 2);
+        assert!((matrix.accuracy() - 5.0 / 8.0).abs() < 1e-12);
+        assert!((matrix.precision(1) - 3.0 / 4.0).abs() < 1e-12);
+        assert!((matrix.recall(1) - 3.0 / 5.0).abs() < 1e-12);
+        assert!(ConfusionMatrix::new(2).accuracy().is_nan());
+    }
+}
@@ -1,3 +1,12 @@
+pub mod header;
+pub mod io;
+pub mod metrics;
+pub mod npy;
+pub mod synthetic;
+pub mod validate;
+
+use std::sync::OnceLock;
+
 /// Possible architecture-dependent features
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SimdInstructionSet {
@@ -28,7 +37,7 @@ pub enum SimdInstructionSet {
     /// Altivec
     #[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
     Altivec,
-    
+
     /// Vector Extensions
     #[cfg(target_arch = "powerpc64")]
     Vsx,
@@ -41,6 +50,19 @@ pub enum SimdInstructionSet {
     None,
 }
 
+impl SimdInstructionSet {
+    /// Steps AVX-512 down to AVX2 and leaves every other set as is. On some
+    /// Skylake-X parts 512-bit instructions lower the core clock, which can make
+    /// short bursts of inference slower than with AVX2.
+    pub fn without_avx512(self) -> Self {
+        match self {
+            #[cfg(target_arch = "x86_64")]
+            SimdInstructionSet::AVX512 => SimdInstructionSet::AVX2,
+            other => other,
+        }
+    }
+}
+
 /// Detects the best available SIMD instruction set
 /// # Safety
 /// actually safe
@@ -99,10 +121,116 @@ pub unsafe fn detect_simd_instruction_set() -> SimdInstructionSet {
     SimdInstructionSet::None
 }
 
-// Prints System Information
+/// Safe form of `detect_simd_instruction_set`, for callers that want to log or
+/// report the detected set. The result is cached.
+pub fn best_simd() -> SimdInstructionSet {
+    static SIMD: OnceLock<SimdInstructionSet> = OnceLock::new();
+    // SAFETY: detection only queries CPU feature flags
+    *SIMD.get_or_init(|| unsafe { detect_simd_instruction_set() })
+}
+
+/// Name of the CPU architecture the library was built for, e.g. "x86_64" or
+/// "aarch64". Native builds run on the same architecture, so this is the host's.
+pub fn detect_cpu_architecture() -> &'static str {
+    std::env::consts::ARCH
+}
+
+/// Returns true when the running CPU can execute `set`
+pub fn is_simd_supported(set: SimdInstructionSet) -> bool {
+    match set {
+        #[cfg(target_arch = "x86_64")]
+        SimdInstructionSet::AVX512 => is_x86_feature_detected!("avx512f"),
+        #[cfg(target_arch = "x86_64")]
+        SimdInstructionSet::AVX2 => is_x86_feature_detected!("avx2"),
+        #[cfg(target_arch = "x86_64")]
+        SimdInstructionSet::AVX => is_x86_feature_detected!("avx"),
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        SimdInstructionSet::SSE4_2 => is_x86_feature_detected!("sse4.2"),
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        SimdInstructionSet::SSE4_1 => is_x86_feature_detected!("sse4.1"),
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        SimdInstructionSet::SSE2 => is_x86_feature_detected!("sse2"),
+        #[cfg(target_arch = "aarch64")]
+        SimdInstructionSet::Neon => is_aarch64_feature_detected!("neon"),
+        SimdInstructionSet::None => true,
+        // The remaining sets are compile-time target features
+        #[allow(unreachable_patterns)]
+        _ => set == best_simd(),
+    }
+}
+
+/// Cache line size assumed when it cannot be detected
+pub const DEFAULT_CACHE_LINE_SIZE: usize = 64;
+
+/// Detects the L1 data cache line size in bytes, falling back to
+/// `DEFAULT_CACHE_LINE_SIZE`. Most x86 cores use 64 bytes, while some ARM
+/// cores (Apple M-series, several server parts) use 128. The result is cached.
+pub fn detect_cache_line_size() -> usize {
+    static LINE_SIZE: OnceLock<usize> = OnceLock::new();
+    *LINE_SIZE.get_or_init(|| {
+        cache_line_size_os()
+            .or_else(cache_line_size_cpuid)
+            .filter(|size| size.is_power_of_two() && (16..=1024).contains(size))
+            .unwrap_or(DEFAULT_CACHE_LINE_SIZE)
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn cache_line_size_os() -> Option<usize> {
+    let path = "/sys/devices/system/cpu/cpu0/cache/index0/coherency_line_size";
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(target_os = "macos")]
+fn cache_line_size_os() -> Option<usize> {
+    let output = std::process::Command::new("sysctl")
+        .args(["-n", "hw.cachelinesize"])
+        .output()
+        .ok()?;
+    String::from_utf8(output.stdout).ok()?.trim().parse().ok()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn cache_line_size_os() -> Option<usize> {
+    None
+}
+
+// CPUID leaf 1 reports the CLFLUSH line size in 8-byte units in EBX[15:8]
+#[cfg(target_arch = "x86_64")]
+fn cache_line_size_cpuid() -> Option<usize> {
+    // SAFETY: CPUID leaf 1 is available on every x86_64 CPU. `__cpuid` is only
+    // a safe function on recent toolchains, hence the allow.
+    #[allow(unused_unsafe)]
+    let ebx = unsafe { std::arch::x86_64::__cpuid(1) }.ebx;
+    Some(((ebx >> 8) & 0xff) as usize * 8)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn cache_line_size_cpuid() -> Option<usize> {
+    None
+}
+
+// Logs system information at debug level
 pub fn print_system_info() {
-    unsafe {
-        let simd = detect_simd_instruction_set();
-        println!("Detected SIMD Instruction Set: {:?}", simd);
+    log::debug!("CPU architecture: {}", detect_cpu_architecture());
+    log::debug!("Detected SIMD Instruction Set: {:?}", best_simd());
+    log::debug!("Cache line size: {} bytes", detect_cache_line_size());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_line_size_is_plausible() {
+        let size = detect_cache_line_size();
+        assert!(size.is_power_of_two() && (16..=1024).contains(&size));
+    }
+
+    #[test]
+    fn best_simd_is_supported() {
+        assert!(is_simd_supported(best_simd()));
+        assert_eq!(best_simd(), unsafe { detect_simd_instruction_set() });
+        assert!(!detect_cpu_architecture().is_empty());
     }
 }
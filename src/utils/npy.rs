@@ -0,0 +1,190 @@
+// File: src/utils/npy.rs
+//
+// Minimal reader for NumPy `.npy` files holding float32 arrays.
+//
+// Format reference: https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html
+// The header is a Python dict literal such as
+//
+//     {'descr': '<f4', 'fortran_order': False, 'shape': (100, 784), }
+//
+// Only f32 arrays are accepted. Fortran ordered 2D arrays are transposed so
+// callers always receive row-major data.
+
+use crate::utils::io::{gather_columns, open_input, Endian};
+use std::io::{self, Read};
+use std::path::Path;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// A float32 array read from a `.npy` file, stored in row-major order
+#[derive(Debug, Clone, PartialEq)]
+pub struct NpyArray {
+    pub data: Vec<f32>,
+    pub shape: Vec<usize>,
+}
+
+impl NpyArray {
+    /// Number of columns for a 2D array, `None` for any other rank
+    pub fn num_columns(&self) -> Option<usize> {
+        match self.shape.as_slice() {
+            [_, columns] => Some(*columns),
+            _ => None,
+        }
+    }
+}
+
+pub fn read_npy(path: &Path) -> io::Result<NpyArray> {
+    let mut file = open_input(path)?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    parse_npy(&bytes)
+}
+
+pub fn parse_npy(bytes: &[u8]) -> io::Result<NpyArray> {
+    if bytes.len() < 10 || &bytes[..6] != MAGIC {
+        return Err(invalid("missing .npy magic string"));
+    }
+
+    // version 1.0 uses a u16 header length, 2.0 and 3.0 use a u32
+    let major = bytes[6];
+    let (header_len, header_start) = match major {
+        1 => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+        2 | 3 if bytes.len() >= 12 => (
+            u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize,
+            12,
+        ),
+        _ => return Err(invalid(format!("unsupported .npy version {}", major))),
+    };
+    let data_start = header_start + header_len;
+    if bytes.len() < data_start {
+        return Err(invalid("truncated .npy header"));
+    }
+    let header = std::str::from_utf8(&bytes[header_start..data_start])
+        .map_err(|_| invalid(".npy header is not valid text"))?;
+
+    let descr = header_value(header, "descr")?;
+    let descr = descr.trim_matches(|c| c == '\'' || c == '"');
+    let endian = match descr {
+        "<f4" | "=f4" | "|f4" => Endian::Little,
+        ">f4" => Endian::Big,
+        other => {
+            return Err(invalid(format!(
+                "unsupported dtype {}, only float32 arrays are supported",
+                other
+            )))
+        }
+    };
+    let fortran_order = match header_value(header, "fortran_order")? {
+        "True" => true,
+        "False" => false,
+        other => return Err(invalid(format!("invalid fortran_order {}", other))),
+    };
+    let shape = parse_shape(header_value(header, "shape")?)?;
+
+    let len: usize = shape.iter().product();
+    let payload = &bytes[data_start..];
+    if payload.len() != len * 4 {
+        return Err(invalid(format!(
+            "shape {:?} needs {} bytes of data, found {}",
+            shape,
+            len * 4,
+            payload.len()
+        )));
+    }
+    let mut data: Vec<f32> = payload
+        .chunks(4)
+        .map(|b| endian.f32_from_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+
+    if fortran_order {
+        if let [_, columns] = shape.as_slice() {
+            data = gather_columns(&data, *columns);
+        } else if shape.len() > 2 {
+            return Err(invalid("fortran ordered arrays above 2D are not supported"));
+        }
+    }
+
+    Ok(NpyArray { data, shape })
+}
+
+// Returns the raw text of `key`'s value in the header dict
+fn header_value<'a>(header: &'a str, key: &str) -> io::Result<&'a str> {
+    let missing = || invalid(format!(".npy header is missing '{}'", key));
+    let start = header
+        .find(&format!("'{}'", key))
+        .or_else(|| header.find(&format!("\"{}\"", key)))
+        .ok_or_else(missing)?;
+    let rest = &header[start + key.len() + 2..];
+    let rest = rest
+        .trim_start()
+        .strip_prefix(':')
+        .ok_or_else(missing)?
+        .trim_start();
+    let end = if rest.starts_with('(') {
+        rest.find(')').map(|i| i + 1)
+    } else {
+        rest.find([',', '}'])
+    }
+    .ok_or_else(missing)?;
+    Ok(rest[..end].trim())
+}
+
+fn parse_shape(value: &str) -> io::Result<Vec<usize>> {
+    let inner = value
+        .strip_prefix('(')
+        .and_then(|v| v.strip_suffix(')'))
+        .ok_or_else(|| invalid(format!("invalid shape {}", value)))?;
+    inner
+        .split(',')
+        .map(str::trim)
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| {
+            dim.parse::<usize>()
+                .map_err(|_| invalid(format!("invalid shape {}", value)))
+        })
+        .collect()
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn npy_bytes(header: &str, values: &[f32]) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend([1, 0]);
+        bytes.extend((header.len() as u16).to_le_bytes());
+        bytes.extend(header.as_bytes());
+        for v in values {
+            bytes.extend(v.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn reads_c_and_fortran_order() {
+        let c = npy_bytes(
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (2, 3), }\n",
+            &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+        );
+        let array = parse_npy(&c).unwrap();
+        assert_eq!(array.shape, vec![2, 3]);
+        assert_eq!(array.num_columns(), Some(3));
+        assert_eq!(array.data, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let f = npy_bytes(
+            "{'descr': '<f4', 'fortran_order': True, 'shape': (2, 3), }\n",
+            &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0],
+        );
+        assert_eq!(parse_npy(&f).unwrap(), array);
+
+        let f8 = npy_bytes(
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (1,), }\n",
+            &[0.0, 0.0],
+        );
+        assert!(parse_npy(&f8).is_err());
+    }
+}
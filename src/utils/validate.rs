@@ -0,0 +1,75 @@
+// File: src/utils/validate.rs
+//
+// Sanity checks for loaded model parameters. A corrupted export usually shows
+// up as NaN or infinite weights, which silently turn every logistic prediction
+// into NaN or 0.5. Subnormal weights are valid but can make SIMD arithmetic
+// dramatically slower on many CPUs, so they are reported separately.
+
+// Number of offending indices listed before the rest are summarized
+const MAX_LISTED: usize = 10;
+
+/// Problems found in a parameter vector. Indices are positions in the scanned
+/// buffer; `scan_params` reports the bias as index `weights.len()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParamReport {
+    pub non_finite: Vec<usize>,
+    pub subnormal: Vec<usize>,
+}
+
+impl ParamReport {
+    pub fn is_clean(&self) -> bool {
+        self.non_finite.is_empty() && self.subnormal.is_empty()
+    }
+}
+
+/// Scans weights and bias for NaN, infinite and subnormal values
+pub fn scan_params(weights: &[f32], bias: f32) -> ParamReport {
+    scan_values(weights.iter().chain(std::iter::once(&bias)))
+}
+
+/// Scans a raw parameter buffer, reporting indices in buffer order
+pub fn scan_values<'a>(values: impl IntoIterator<Item = &'a f32>) -> ParamReport {
+    let mut report = ParamReport::default();
+    for (i, &value) in values.into_iter().enumerate() {
+        if !value.is_finite() {
+            report.non_finite.push(i);
+        } else if value.is_subnormal() {
+            report.subnormal.push(i);
+        }
+    }
+    report
+}
+
+/// Formats indices for a log line, listing only the first few
+pub fn describe_indices(indices: &[usize]) -> String {
+    let listed: Vec<String> = indices
+        .iter()
+        .take(MAX_LISTED)
+        .map(|i| i.to_string())
+        .collect();
+    let mut description = listed.join(", ");
+    if indices.len() > MAX_LISTED {
+        description.push_str(&format!(" and {} more", indices.len() - MAX_LISTED));
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_non_finite_and_subnormal_values() {
+        let weights = [1.0, f32::NAN, 1e-40, f32::INFINITY, -0.5];
+        let report = scan_params(&weights, f32::NEG_INFINITY);
+        assert_eq!(report.non_finite, vec![1, 3, 5]);
+        assert_eq!(report.subnormal, vec![2]);
+        assert!(scan_params(&[0.0, 1.0], 0.5).is_clean());
+
+        let many: Vec<usize> = (0..12).collect();
+        assert_eq!(
+            describe_indices(&many),
+            "0, 1, 2, 3, 4, 5, 6, 7, 8, 9 and 2 more"
+        );
+    }
+}